@@ -1,25 +1,87 @@
 extern crate libchao;
 extern crate rustyline;
 
+use std::io::Read;
+use std::process;
+
 use rustyline::{DefaultEditor, error::ReadlineError};
 
-use libchao::{Interpreter, parse};
+use libchao::{Expr, Interpreter};
+
+const USAGE: &str = "usage: chao [file | -e expr | -] [args...]";
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (name, source, script_args, print_result) = match args.as_slice() {
+        [] => return repl(),
+        [flag, ..] if flag == "-h" || flag == "--help" => {
+            println!("{}", USAGE);
+            return;
+        }
+        [flag, expr, rest @ ..] if flag == "-e" => ("<expr>".to_string(), expr.clone(), rest, true),
+        [flag, rest @ ..] if flag == "-" => {
+            let mut source = String::new();
+            if let Err(err) = std::io::stdin().read_to_string(&mut source) {
+                fail(&format!("<stdin>: {}", err));
+            }
+            ("<stdin>".to_string(), source, rest, false)
+        }
+        [flag, ..] if flag.starts_with('-') => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        [path, rest @ ..] => match std::fs::read_to_string(path) {
+            Ok(source) => (path.clone(), source, rest, false),
+            Err(err) => fail(&format!("{}: {}", path, err)),
+        },
+    };
+
+    let mut interpreter = Interpreter::new();
+    let script_args: Vec<Expr> = script_args.iter().cloned().map(Expr::Str).collect();
+    if script_args.is_empty() {
+        interpreter.define("*args*", Expr::Nil);
+    } else {
+        interpreter.define("*args*", Expr::List(script_args));
+    }
+
+    let source = strip_shebang(&source);
+    match interpreter.eval_str(&source) {
+        Ok(result) if print_result && result != Expr::Nil => println!("{:?}", result),
+        Ok(_) => {}
+        Err(err) => fail(&err.render(&source, &name)),
+    }
+}
+
+// Blanks out a `#!` line rather than removing it so that error positions
+// still match the file.
+fn strip_shebang(source: &str) -> String {
+    match source.strip_prefix("#!") {
+        Some(rest) => rest
+            .find('\n')
+            .map_or(String::new(), |i| rest[i..].to_string()),
+        None => source.to_string(),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn repl() {
     let mut rl = DefaultEditor::new().unwrap();
     rl.load_history(".chaohistory").unwrap_or_default();
     let mut interpreter = Interpreter::new();
+    interpreter.define("*args*", Expr::Nil);
     loop {
         let readline = rl.readline("chao> ");
         match readline {
             Ok(line) => {
                 rl.add_history_entry(&line).unwrap();
-                match parse(&line) {
-                    Ok(expr) => match interpreter.eval(&expr) {
-                        Ok(result) => println!("=> {}", result),
-                        Err(err) => println!("error: {:?}", err),
-                    },
-                    Err(err) => println!("{:?}", err),
+                match interpreter.eval_str(&line) {
+                    Ok(result) => println!("=> {}", result),
+                    Err(err) => println!("{}", err.render(&line, "<repl>")),
                 }
             }
             Err(ReadlineError::Interrupted) => continue,
@@ -1,9 +1,25 @@
+mod bytes;
+mod coll;
+mod list;
+mod math;
+mod seq;
+mod string;
+mod types;
+
+pub(crate) use coll::lookup;
+
+use std::cmp::Ordering;
+
 use crate::{
     Interpreter,
     env::Env,
     expr::Expr::{self, *},
-    functions::{Arity, Builtin, Callable, EvalMode, Lambda, LambdaParams, Macro},
+    functions::{
+        Arity, Builtin, Callable, EvalMode, Lambda, LambdaParams, Macro, SpecialForm,
+        SpecialFormFn, Tail,
+    },
     interpreter::{EvalError, EvalResult},
+    number,
 };
 
 fn insert_builtin(
@@ -19,25 +35,111 @@ fn insert_builtin(
     );
 }
 
+fn insert_special_form(env: &mut Env, name: &str, arity: Arity, f: SpecialFormFn) {
+    env.insert(
+        name.to_string(),
+        Expr::Callable(Callable::SpecialForm(SpecialForm { arity, f })),
+    );
+}
+
 pub fn load(env: &mut Env) {
     insert_builtin(env, "+", EvalMode::Eager, Arity::Any, add);
     insert_builtin(env, "-", EvalMode::Eager, Arity::AtLeast(1), sub);
     insert_builtin(env, "*", EvalMode::Eager, Arity::Any, mul);
     insert_builtin(env, "/", EvalMode::Eager, Arity::AtLeast(1), div);
     insert_builtin(env, "=", EvalMode::Eager, Arity::AtLeast(1), eq);
-    insert_builtin(env, ">", EvalMode::Eager, Arity::Exact(2), gt);
-    insert_builtin(env, "<", EvalMode::Eager, Arity::Exact(2), lt);
-    insert_builtin(env, "if", EvalMode::Raw, Arity::Exact(3), iff);
-    insert_builtin(env, "list", EvalMode::Eager, Arity::Any, list);
+    insert_builtin(env, "!=", EvalMode::Eager, Arity::Exact(2), not_eq);
+    insert_builtin(env, "<", EvalMode::Eager, Arity::AtLeast(1), lt);
+    insert_builtin(env, ">", EvalMode::Eager, Arity::AtLeast(1), gt);
+    insert_builtin(env, "<=", EvalMode::Eager, Arity::AtLeast(1), le);
+    insert_builtin(env, ">=", EvalMode::Eager, Arity::AtLeast(1), ge);
+    insert_special_form(env, "if", Arity::Exact(3), iff);
+    insert_special_form(env, "and", Arity::Any, and);
+    insert_special_form(env, "or", Arity::Any, or);
+    insert_special_form(env, "cond", Arity::Any, cond);
+    insert_special_form(env, "case", Arity::AtLeast(1), case);
+    insert_special_form(env, "when", Arity::AtLeast(1), when);
+    insert_special_form(env, "unless", Arity::AtLeast(1), unless);
+    insert_special_form(env, "do", Arity::Any, do_);
+    insert_special_form(env, "begin", Arity::Any, do_);
+    insert_special_form(env, "try", Arity::AtLeast(1), try_);
+    insert_builtin(env, "error", EvalMode::Eager, Arity::AtLeast(1), error);
+    insert_builtin(env, "error?", EvalMode::Eager, Arity::Exact(1), is_error);
+    insert_builtin(
+        env,
+        "error-kind",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        error_kind,
+    );
+    insert_builtin(
+        env,
+        "error-message",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        error_message,
+    );
+    insert_builtin(
+        env,
+        "error-payload",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        error_payload,
+    );
+    insert_special_form(env, "let", Arity::AtLeast(2), let_);
+    insert_special_form(env, "let*", Arity::AtLeast(2), let_star);
+    insert_special_form(env, "letrec", Arity::AtLeast(2), letrec);
+    insert_builtin(env, "bound?", EvalMode::Eager, Arity::Exact(1), is_bound);
     insert_builtin(env, "intern", EvalMode::Eager, Arity::Exact(1), intern);
-    insert_builtin(env, "lambda", EvalMode::Raw, Arity::Exact(2), lambda);
+    insert_builtin(env, "lambda", EvalMode::Raw, Arity::AtLeast(2), lambda);
     insert_builtin(env, "set", EvalMode::Raw, Arity::Exact(2), set);
     insert_builtin(env, "def", EvalMode::Raw, Arity::AtLeast(2), def);
-    insert_builtin(env, "defmacro", EvalMode::Raw, Arity::Exact(3), defmacro);
+    insert_builtin(env, "defmacro", EvalMode::Raw, Arity::AtLeast(3), defmacro);
+    insert_builtin(env, "gensym", EvalMode::Eager, Arity::Any, gensym);
+    insert_builtin(
+        env,
+        "macroexpand-1",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        macroexpand_1,
+    );
+    insert_builtin(
+        env,
+        "macroexpand",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        macroexpand,
+    );
+    insert_builtin(
+        env,
+        "macroexpand-all",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        macroexpand_all,
+    );
+
+    list::load(env);
+    coll::load(env);
+    math::load(env);
+    seq::load(env);
+    string::load(env);
+    types::load(env);
+    bytes::load(env);
+}
+
+// The arithmetic folds combine running totals, so a type error is reported
+// with the arguments the operator was actually given.
+fn with_args(op: &str, args: &[Expr]) -> impl FnOnce(EvalError) -> EvalError {
+    move |err| match err {
+        EvalError::TypeError { .. } => EvalError::type_error(op, args),
+        err => err,
+    }
 }
 
 fn add(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
-    args.iter().try_fold(Int(0), |acc, x| acc + x.clone())
+    args.iter()
+        .try_fold(Int(0), |acc, x| acc + x.clone())
+        .map_err(with_args("+", args))
 }
 
 fn sub(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
@@ -46,10 +148,13 @@ fn sub(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
         [head, tail @ ..] => tail.iter().try_fold(head.clone(), |acc, x| acc - x.clone()),
         [] => Err(EvalError::ArityMismatch),
     }
+    .map_err(with_args("-", args))
 }
 
 fn mul(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
-    args.iter().try_fold(Int(1), |acc, x| acc * x.clone())
+    args.iter()
+        .try_fold(Int(1), |acc, x| acc * x.clone())
+        .map_err(with_args("*", args))
 }
 
 fn div(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
@@ -58,66 +163,379 @@ fn div(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
         [head, tail @ ..] => tail.iter().try_fold(head.clone(), |acc, x| acc / x.clone()),
         [] => Err(EvalError::ArityMismatch),
     }
+    .map_err(with_args("/", args))
 }
 
-fn iff(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+fn iff<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
     let [cond, then_branch, else_branch] = args else {
         return Err(EvalError::ArityMismatch);
     };
-    if interpreter.eval(cond)?.is_truthy() {
-        interpreter.eval(then_branch)
+    let branch = if interpreter.eval(cond)?.is_truthy() {
+        then_branch
+    } else {
+        else_branch
+    };
+    Ok(Tail::Eval(branch, interpreter.env.clone()))
+}
+
+fn and<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [init @ .., last] = args else {
+        return Ok(Tail::Value(Bool(true)));
+    };
+
+    for arg in init {
+        let value = interpreter.eval(arg)?;
+        if value.is_falsy() {
+            return Ok(Tail::Value(value));
+        }
+    }
+    Ok(Tail::Eval(last, interpreter.env.clone()))
+}
+
+fn or<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [init @ .., last] = args else {
+        return Ok(Tail::Value(Nil));
+    };
+
+    for arg in init {
+        let value = interpreter.eval(arg)?;
+        if value.is_truthy() {
+            return Ok(Tail::Value(value));
+        }
+    }
+    Ok(Tail::Eval(last, interpreter.env.clone()))
+}
+
+fn is_else(expr: &Expr) -> bool {
+    matches!(expr, Symbol(name) if name == "else")
+}
+
+fn cond<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    for clause in args {
+        let List(clause) = clause else {
+            return Err(EvalError::ArgumentError);
+        };
+        let [test, body @ ..] = clause.as_slice() else {
+            return Err(EvalError::ArgumentError);
+        };
+
+        if is_else(test) {
+            let env = interpreter.env.clone();
+            return interpreter.eval_body(body, env);
+        }
+
+        let value = interpreter.eval(test)?;
+        if value.is_truthy() {
+            if body.is_empty() {
+                return Ok(Tail::Value(value));
+            }
+            let env = interpreter.env.clone();
+            return interpreter.eval_body(body, env);
+        }
+    }
+    Ok(Tail::Value(Nil))
+}
+
+fn case<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [key, clauses @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let key = interpreter.eval(key)?;
+    for clause in clauses {
+        let List(clause) = clause else {
+            return Err(EvalError::ArgumentError);
+        };
+        let [datums, body @ ..] = clause.as_slice() else {
+            return Err(EvalError::ArgumentError);
+        };
+
+        let matches = match datums {
+            datums if is_else(datums) => true,
+            List(datums) => datums.contains(&key),
+            datum => *datum == key,
+        };
+
+        if matches {
+            let env = interpreter.env.clone();
+            return interpreter.eval_body(body, env);
+        }
+    }
+    Ok(Tail::Value(Nil))
+}
+
+fn when<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [test, body @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    if interpreter.eval(test)?.is_truthy() {
+        let env = interpreter.env.clone();
+        interpreter.eval_body(body, env)
+    } else {
+        Ok(Tail::Value(Nil))
+    }
+}
+
+fn unless<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [test, body @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    if interpreter.eval(test)?.is_falsy() {
+        let env = interpreter.env.clone();
+        interpreter.eval_body(body, env)
     } else {
-        interpreter.eval(else_branch)
+        Ok(Tail::Value(Nil))
     }
 }
 
-fn list(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
-    Ok(List(args.to_vec()))
+fn do_<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let env = interpreter.env.clone();
+    interpreter.eval_body(args, env)
+}
+
+fn try_<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let is_clause = |expr: &Expr, name: &str| match expr {
+        List(items) => matches!(items.first(), Some(Symbol(head)) if head == name),
+        _ => false,
+    };
+
+    let (args, finally) = match args.split_last() {
+        Some((last @ List(clause), init)) if is_clause(last, "finally") => (init, &clause[1..]),
+        _ => (args, [].as_slice()),
+    };
+    let split = args
+        .iter()
+        .rposition(|arg| !is_clause(arg, "catch"))
+        .map_or(0, |i| i + 1);
+    let (body, catches) = args.split_at(split);
+
+    let mut handlers = vec![];
+    for catch in catches {
+        let List(catch) = catch else {
+            return Err(EvalError::ArgumentError);
+        };
+        match &catch[1..] {
+            [Keyword(kind), Symbol(name), handler @ ..] => {
+                handlers.push((Some(kind), name, handler))
+            }
+            [Quote(kind), Symbol(name), handler @ ..] => match kind.as_ref() {
+                Symbol(kind) => handlers.push((Some(kind), name, handler)),
+                _ => return Err(EvalError::ArgumentError),
+            },
+            [Symbol(name), handler @ ..] => handlers.push((None, name, handler)),
+            _ => return Err(EvalError::ArgumentError),
+        }
+    }
+
+    let result = match interpreter.eval_program(body) {
+        Err(err) => {
+            let handler = handlers
+                .into_iter()
+                .find(|(kind, _, _)| kind.is_none_or(|kind| kind == err.kind_name()));
+
+            match handler {
+                Some((_, name, handler)) => {
+                    let err = Error(Box::new(err.kind().clone()));
+                    let env = interpreter.env.child_with(vec![(name.clone(), err)]);
+                    interpreter.with_env(env, |interpreter| interpreter.eval_program(handler))
+                }
+                None => Err(err),
+            }
+        }
+        ok => ok,
+    };
+
+    interpreter.eval_program(finally)?;
+    result.map(Tail::Value)
+}
+
+fn error(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (kind, message, payload) = match args {
+        [Error(err)] => return Err(*err.clone()),
+        [Str(message)] => ("error", message, Nil),
+        [Str(message), payload] => ("error", message, payload.clone()),
+        [Symbol(kind) | Keyword(kind), Str(message)] => (kind.as_str(), message, Nil),
+        [Symbol(kind) | Keyword(kind), Str(message), payload] => {
+            (kind.as_str(), message, payload.clone())
+        }
+        _ => return Err(EvalError::type_error("error", args)),
+    };
+
+    Err(EvalError::User {
+        kind: kind.to_string(),
+        message: message.clone(),
+        payload: Box::new(payload),
+    })
+}
+
+fn is_error(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Bool(matches!(args, [Error(_)])))
+}
+
+fn error_kind(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Error(err)] => Ok(Symbol(err.kind_name().to_string())),
+        _ => Err(EvalError::type_error("error-kind", args)),
+    }
+}
+
+fn error_message(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Error(err)] => Ok(Str(err.to_string())),
+        _ => Err(EvalError::type_error("error-message", args)),
+    }
+}
+
+fn error_payload(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Error(err)] => Ok(err.payload()),
+        _ => Err(EvalError::type_error("error-payload", args)),
+    }
+}
+
+fn parse_bindings(bindings: &Expr) -> EvalResult<Vec<(&String, &Expr)>> {
+    let bindings = match bindings {
+        Nil => return Ok(vec![]),
+        List(bindings) => bindings,
+        _ => return Err(EvalError::ArgumentError),
+    };
+
+    bindings
+        .iter()
+        .map(|binding| match binding {
+            List(pair) => match pair.as_slice() {
+                [Symbol(name), value] => Ok((name, value)),
+                [_, _] => Err(EvalError::VariableNameMustBeSymbol),
+                _ => Err(EvalError::ArgumentError),
+            },
+            _ => Err(EvalError::ArgumentError),
+        })
+        .collect()
+}
+
+fn let_<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [bindings, body @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let bindings = parse_bindings(bindings)?
+        .into_iter()
+        .map(|(name, value)| Ok((name.clone(), interpreter.eval(value)?)))
+        .collect::<EvalResult<Vec<_>>>()?;
+
+    let env = interpreter.env.child_with(bindings);
+    interpreter.eval_body(body, env)
+}
+
+fn let_star<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [bindings, body @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let mut env = interpreter.env.child();
+    for (name, value) in parse_bindings(bindings)? {
+        let value = interpreter.with_env(env.clone(), |interpreter| interpreter.eval(value))?;
+        env.insert(name.clone(), value);
+    }
+
+    interpreter.eval_body(body, env)
+}
+
+fn letrec<'a>(interpreter: &mut Interpreter, args: &'a [Expr]) -> EvalResult<Tail<'a>> {
+    let [bindings, body @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let bindings = parse_bindings(bindings)?;
+    let mut env = interpreter.env.child_with(
+        bindings
+            .iter()
+            .map(|(name, _)| ((*name).clone(), Nil))
+            .collect(),
+    );
+    for (name, value) in bindings {
+        let value = interpreter.with_env(env.clone(), |interpreter| interpreter.eval(value))?;
+        env.insert(name.clone(), value);
+    }
+
+    interpreter.eval_body(body, env)
+}
+
+// Numbers are equal when they have the same value, whatever their
+// representation; everything else is compared structurally.
+fn equal(a: &Expr, b: &Expr) -> bool {
+    if a.is_number() && b.is_number() {
+        number::compare(a, b) == Some(Ordering::Equal)
+    } else {
+        a == b
+    }
 }
 
 fn eq(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
     match args {
         [head] if head.is_falsy() => Ok(Bool(false)),
         [_head] => Ok(Bool(true)),
-        [head, tail @ ..] => Ok(Bool(tail.iter().all(|x| x == head))),
+        [head, tail @ ..] => Ok(Bool(tail.iter().all(|x| equal(head, x)))),
         [] => Err(EvalError::ArityMismatch),
     }
 }
 
-fn lt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+fn not_eq(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
     match args {
-        [a, b] => Ok(Bool(a < b)),
+        [a, b] => Ok(Bool(!equal(a, b))),
         _ => Err(EvalError::ArityMismatch),
     }
 }
 
-fn gt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
-    match args {
-        [a, b] => Ok(Bool(a > b)),
-        _ => Err(EvalError::ArityMismatch),
+fn compare_chain(op: &str, args: &[Expr], accept: fn(Ordering) -> bool) -> EvalResult<Expr> {
+    for pair in args.windows(2) {
+        match pair[0].partial_cmp(&pair[1]) {
+            Some(ordering) if accept(ordering) => {}
+            Some(_) => return Ok(Bool(false)),
+            None => return Err(EvalError::type_error(op, pair)),
+        }
     }
+    Ok(Bool(true))
+}
+
+fn lt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    compare_chain("<", args, Ordering::is_lt)
+}
+
+fn gt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    compare_chain(">", args, Ordering::is_gt)
+}
+
+fn le(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    compare_chain("<=", args, Ordering::is_le)
+}
+
+fn ge(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    compare_chain(">=", args, Ordering::is_ge)
 }
 
 fn lambda(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
     match args {
-        [List(params), body] => {
-            let params = parse_lambda_params(params)?;
-
-            Ok(Callable(Callable::Lambda(Lambda {
-                params,
-                body: Box::new(body.clone()),
-                env: interpreter.env.clone(),
-            })))
-        }
-        [Nil, body] => Ok(Callable(Callable::Lambda(Lambda {
-            params: LambdaParams::Fixed(vec![]),
-            body: Box::new(body.clone()),
-            env: interpreter.env.clone(),
-        }))),
+        [params @ (List(_) | Nil), body @ ..] => make_lambda(interpreter, params, body),
         _ => Err(EvalError::LambdaNameMustBeSymbol),
     }
 }
 
+fn make_lambda(interpreter: &mut Interpreter, params: &Expr, body: &[Expr]) -> EvalResult<Expr> {
+    let params = match params {
+        List(params) => parse_lambda_params(params)?,
+        _ => LambdaParams::Fixed(vec![]),
+    };
+
+    Ok(Callable(Callable::Lambda(Lambda {
+        params,
+        body: interpreter.source_of(body),
+        env: interpreter.env.clone(),
+    })))
+}
+
 fn set(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
     match args {
         [Symbol(name), value_expr] => {
@@ -126,15 +544,24 @@ fn set(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
             if interpreter.env.assign(name, value.clone()) {
                 Ok(value)
             } else {
-                Err(EvalError::UnboundVariable)
+                Err(EvalError::unbound(name, &interpreter.env))
             }
         }
         _ => Err(EvalError::VariableNameMustBeSymbol),
     }
 }
 
+fn is_bound(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Symbol(name)] => Ok(Bool(interpreter.env.contains(name))),
+        _ => Err(EvalError::type_error("bound?", args)),
+    }
+}
+
 fn intern(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
     match args {
+        // Names containing `#` are reserved for `gensym`.
+        [Str(s)] if s.contains('#') => Err(EvalError::type_error("intern", args)),
         [Str(s)] => Ok(Symbol(s.clone())),
         [_] => Err(EvalError::CanOnlyInterStrings),
         _ => Err(EvalError::ArityMismatch),
@@ -148,25 +575,8 @@ fn def(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
             interpreter.env.insert(name.clone(), value.clone());
             Ok(value)
         }
-        [Symbol(name), List(params), body] => {
-            let params = parse_lambda_params(params)?;
-
-            let value = Callable(Callable::Lambda(Lambda {
-                params,
-                body: Box::new(body.clone()),
-                env: interpreter.env.clone(),
-            }));
-
-            interpreter.env.insert(name.clone(), value.clone());
-            Ok(value)
-        }
-        [Symbol(name), Nil, body] => {
-            let value = Callable(Callable::Lambda(Lambda {
-                params: LambdaParams::Fixed(vec![]),
-                body: Box::new(body.clone()),
-                env: interpreter.env.clone(),
-            }));
-
+        [Symbol(name), params @ (List(_) | Nil), body @ ..] => {
+            let value = make_lambda(interpreter, params, body)?;
             interpreter.env.insert(name.clone(), value.clone());
             Ok(value)
         }
@@ -175,28 +585,122 @@ fn def(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
 }
 
 fn parse_lambda_params(params: &[Expr]) -> EvalResult<LambdaParams> {
-    let mut arg_names = vec![];
+    #[derive(PartialEq, PartialOrd)]
+    enum Section {
+        Required,
+        Optional,
+        Rest,
+        Key,
+    }
+
+    let mut section = Section::Required;
+    let mut required = vec![];
+    let mut optional = vec![];
+    let mut rest = None;
+    let mut keys = vec![];
+
+    let with_default = |param: &Expr| match param {
+        Symbol(name) => Ok((name.clone(), Nil)),
+        List(spec) => match spec.as_slice() {
+            [Symbol(name), default] => Ok((name.clone(), default.clone())),
+            _ => Err(EvalError::DefParamMustBeSymbol),
+        },
+        _ => Err(EvalError::DefParamMustBeSymbol),
+    };
+
     for param in params {
-        match param {
-            Symbol(name) => arg_names.push(name.clone()),
-            _ => return Err(EvalError::DefParamMustBeSymbol),
+        let next = match param {
+            Symbol(name) if name == "&optional" => Some(Section::Optional),
+            Symbol(name) if name == "&rest" => Some(Section::Rest),
+            Symbol(name) if name == "&key" => Some(Section::Key),
+            _ => None,
+        };
+
+        if let Some(next) = next {
+            if section == Section::Rest && rest.is_none() {
+                return Err(EvalError::MissingVarArgs);
+            }
+            if next <= section {
+                return Err(EvalError::DefParamMustBeSymbol);
+            }
+            section = next;
+            continue;
+        }
+
+        match section {
+            Section::Required => match param {
+                Symbol(name) => required.push(name.clone()),
+                _ => return Err(EvalError::DefParamMustBeSymbol),
+            },
+            Section::Optional => optional.push(with_default(param)?),
+            Section::Rest => match (param, &rest) {
+                (Symbol(name), None) => rest = Some(name.clone()),
+                _ => return Err(EvalError::DefParamMustBeSymbol),
+            },
+            Section::Key => keys.push(with_default(param)?),
         }
     }
 
-    Ok(LambdaParams::Fixed(arg_names))
+    if section == Section::Rest && rest.is_none() {
+        return Err(EvalError::MissingVarArgs);
+    }
+
+    if section == Section::Required {
+        return Ok(LambdaParams::Fixed(required));
+    }
+
+    Ok(LambdaParams::Variadic {
+        required,
+        optional,
+        rest,
+        keys,
+    })
 }
 
 fn defmacro(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
-    let [Symbol(name), List(params), body] = args else {
-        return Err(EvalError::ArgumentError);
+    let (name, params, body) = match args {
+        [Symbol(name), List(params), body @ ..] => (name, params.as_slice(), body),
+        [Symbol(name), Nil, body @ ..] => (name, [].as_slice(), body),
+        _ => return Err(EvalError::ArgumentError),
     };
 
     let value = Expr::Callable(Callable::Macro(Macro {
         params: parse_lambda_params(params)?,
-        body: Box::new(body.clone()),
+        body: interpreter.source_of(body),
         env: interpreter.env.clone(),
     }));
 
     interpreter.env.insert(name.clone(), value.clone());
     Ok(value)
 }
+
+fn gensym(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [] => Ok(interpreter.gensym("G")),
+        [Str(prefix)] | [Symbol(prefix)] => Ok(interpreter.gensym(prefix)),
+        _ => Err(EvalError::type_error("gensym", args)),
+    }
+}
+
+fn macroexpand_1(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [form] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    Ok(interpreter
+        .macroexpand_1(form)?
+        .unwrap_or_else(|| form.clone()))
+}
+
+fn macroexpand(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [form] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    interpreter.macroexpand(form)
+}
+
+fn macroexpand_all(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [form] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    interpreter.expand_all(form)
+}
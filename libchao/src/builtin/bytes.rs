@@ -0,0 +1,280 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use num_bigint::{BigInt, Sign};
+
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, EvalMode},
+    interpreter::{EvalError, EvalResult},
+    number,
+};
+
+use super::{insert_builtin, list::from_vec, string::char_index};
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "bytes", EvalMode::Eager, Arity::Any, bytes);
+    insert_builtin(
+        env,
+        "bytes->list",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        bytes_to_list,
+    );
+    insert_builtin(
+        env,
+        "subbytes",
+        EvalMode::Eager,
+        Arity::AtLeast(2),
+        subbytes,
+    );
+    insert_builtin(
+        env,
+        "bytes-append",
+        EvalMode::Eager,
+        Arity::Any,
+        bytes_append,
+    );
+    insert_builtin(
+        env,
+        "string->bytes",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        string_to_bytes,
+    );
+    insert_builtin(
+        env,
+        "bytes->string",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        bytes_to_string,
+    );
+    insert_builtin(
+        env,
+        "bytes->hex",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        bytes_to_hex,
+    );
+    insert_builtin(
+        env,
+        "hex->bytes",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        hex_to_bytes,
+    );
+    insert_builtin(
+        env,
+        "bytes->base64",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        bytes_to_base64,
+    );
+    insert_builtin(
+        env,
+        "base64->bytes",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        base64_to_bytes,
+    );
+    insert_builtin(
+        env,
+        "int->bytes",
+        EvalMode::Eager,
+        Arity::AtLeast(2),
+        int_to_bytes,
+    );
+    insert_builtin(
+        env,
+        "bytes->int",
+        EvalMode::Eager,
+        Arity::AtLeast(1),
+        bytes_to_int,
+    );
+}
+
+fn bytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    args.iter()
+        .map(|arg| match arg {
+            Int(n) => u8::try_from(*n).map_err(|_| EvalError::type_error("bytes", args)),
+            _ => Err(EvalError::type_error("bytes", args)),
+        })
+        .collect::<EvalResult<Vec<u8>>>()
+        .map(Bytes)
+}
+
+fn bytes_to_list(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Bytes(bytes)] => Ok(from_vec(
+            bytes.iter().map(|byte| Int(*byte as i64)).collect(),
+        )),
+        _ => Err(EvalError::type_error("bytes->list", args)),
+    }
+}
+
+fn subbytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (bytes, start, end) = match args {
+        [Bytes(bytes), Int(start)] => (bytes, *start, None),
+        [Bytes(bytes), Int(start), Int(end)] => (bytes, *start, Some(*end)),
+        _ => return Err(EvalError::type_error("subbytes", args)),
+    };
+
+    let len = bytes.len();
+    let start = char_index(start, len)?;
+    let end = match end {
+        Some(end) => char_index(end, len)?,
+        None => len,
+    };
+    if end < start {
+        return Err(EvalError::IndexOutOfBounds {
+            index: end as i64,
+            len,
+        });
+    }
+
+    Ok(Bytes(bytes[start..end].to_vec()))
+}
+
+fn bytes_append(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let mut result = vec![];
+    for arg in args {
+        match arg {
+            Bytes(bytes) => result.extend_from_slice(bytes),
+            _ => return Err(EvalError::type_error("bytes-append", args)),
+        }
+    }
+    Ok(Bytes(result))
+}
+
+fn string_to_bytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(Bytes(s.as_bytes().to_vec())),
+        _ => Err(EvalError::type_error("string->bytes", args)),
+    }
+}
+
+fn bytes_to_string(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Bytes(bytes)] => {
+            String::from_utf8(bytes.clone())
+                .map(Str)
+                .map_err(|_| EvalError::InvalidEncoding {
+                    encoding: "UTF-8",
+                    data: Box::new(args[0].clone()),
+                })
+        }
+        _ => Err(EvalError::type_error("bytes->string", args)),
+    }
+}
+
+fn bytes_to_hex(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Bytes(bytes)] => Ok(Str(hex::encode(bytes))),
+        _ => Err(EvalError::type_error("bytes->hex", args)),
+    }
+}
+
+fn hex_to_bytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => hex::decode(s)
+            .map(Bytes)
+            .map_err(|_| EvalError::InvalidEncoding {
+                encoding: "hex",
+                data: Box::new(args[0].clone()),
+            }),
+        _ => Err(EvalError::type_error("hex->bytes", args)),
+    }
+}
+
+fn bytes_to_base64(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Bytes(bytes)] => Ok(Str(BASE64.encode(bytes))),
+        _ => Err(EvalError::type_error("bytes->base64", args)),
+    }
+}
+
+fn base64_to_bytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => BASE64
+            .decode(s)
+            .map(Bytes)
+            .map_err(|_| EvalError::InvalidEncoding {
+                encoding: "base64",
+                data: Box::new(args[0].clone()),
+            }),
+        _ => Err(EvalError::type_error("base64->bytes", args)),
+    }
+}
+
+// Byte order is given as `:big` (the default) or `:little`.
+fn big_endian(op: &str, args: &[Expr], endian: Option<&Expr>) -> EvalResult<bool> {
+    match endian {
+        None => Ok(true),
+        Some(Keyword(name) | Symbol(name)) if name == "big" => Ok(true),
+        Some(Keyword(name) | Symbol(name)) if name == "little" => Ok(false),
+        _ => Err(EvalError::type_error(op, args)),
+    }
+}
+
+// The result is allocated up front, so the width is capped to keep a typo
+// from exhausting memory.
+const MAX_WIDTH: i64 = 1 << 24;
+
+// Negative integers are written in two's complement, so `width` bytes hold
+// anything from the smallest signed to the largest unsigned value.
+fn int_to_bytes(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (value, width, endian) = match args {
+        [value, Int(width)] => (value, *width, None),
+        [value, Int(width), endian] => (value, *width, Some(endian)),
+        _ => return Err(EvalError::type_error("int->bytes", args)),
+    };
+    let n = number::to_bigint(value).ok_or_else(|| EvalError::type_error("int->bytes", args))?;
+    if width > MAX_WIDTH {
+        return Err(EvalError::LimitExceeded {
+            op: "int->bytes",
+            value: width,
+            limit: MAX_WIDTH,
+        });
+    }
+    let width = usize::try_from(width)
+        .ok()
+        .filter(|width| *width > 0)
+        .ok_or_else(|| EvalError::type_error("int->bytes", args))?;
+    let big = big_endian("int->bytes", args, endian)?;
+
+    let negative = n.sign() == Sign::Minus;
+    let mut bytes = if negative {
+        n.to_signed_bytes_le()
+    } else {
+        n.to_bytes_le().1
+    };
+    if bytes.len() > width {
+        return Err(EvalError::IntegerOverflow {
+            value: Box::new(value.clone()),
+            width,
+        });
+    }
+    bytes.resize(width, if negative { 0xff } else { 0 });
+    if big {
+        bytes.reverse();
+    }
+
+    Ok(Bytes(bytes))
+}
+
+fn bytes_to_int(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (bytes, endian, signed) = match args {
+        [Bytes(bytes)] => (bytes, None, false),
+        [Bytes(bytes), endian] => (bytes, Some(endian), false),
+        [Bytes(bytes), endian, signed] => (bytes, Some(endian), signed.is_truthy()),
+        _ => return Err(EvalError::type_error("bytes->int", args)),
+    };
+
+    let n = match (big_endian("bytes->int", args, endian)?, signed) {
+        (true, true) => BigInt::from_signed_bytes_be(bytes),
+        (true, false) => BigInt::from_bytes_be(Sign::Plus, bytes),
+        (false, true) => BigInt::from_signed_bytes_le(bytes),
+        (false, false) => BigInt::from_bytes_le(Sign::Plus, bytes),
+    };
+    Ok(number::from_bigint(n))
+}
@@ -0,0 +1,186 @@
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::{
+        Expr::{self, *},
+        Key,
+    },
+    functions::{Arity, EvalMode},
+    interpreter::{EvalError, EvalResult},
+};
+
+use super::{
+    insert_builtin,
+    list::{from_vec, items},
+    seq::callable,
+};
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "vector", EvalMode::Eager, Arity::Any, vector);
+    insert_builtin(
+        env,
+        "list->vector",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        list_to_vector,
+    );
+    insert_builtin(
+        env,
+        "vector->list",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        vector_to_list,
+    );
+    insert_builtin(env, "get", EvalMode::Eager, Arity::AtLeast(2), get);
+    insert_builtin(env, "assoc", EvalMode::Eager, Arity::AtLeast(3), assoc);
+    insert_builtin(env, "dissoc", EvalMode::Eager, Arity::AtLeast(1), dissoc);
+    insert_builtin(env, "keys", EvalMode::Eager, Arity::Exact(1), keys);
+    insert_builtin(env, "vals", EvalMode::Eager, Arity::Exact(1), vals);
+    insert_builtin(env, "contains?", EvalMode::Eager, Arity::Exact(2), contains);
+    insert_builtin(env, "update", EvalMode::Eager, Arity::AtLeast(3), update);
+    insert_builtin(env, "merge", EvalMode::Eager, Arity::Any, merge);
+}
+
+fn vector(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Vector(args.iter().cloned().collect()))
+}
+
+fn list_to_vector(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Vector(
+        items("list->vector", &args[0])?.iter().cloned().collect(),
+    ))
+}
+
+fn vector_to_list(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Vector(items)] => Ok(from_vec(items.iter().cloned().collect())),
+        _ => Err(EvalError::type_error("vector->list", args)),
+    }
+}
+
+fn index(items: &im_rc::Vector<Expr>, key: &Expr) -> Option<usize> {
+    match key {
+        Int(i) => usize::try_from(*i).ok().filter(|i| *i < items.len()),
+        _ => None,
+    }
+}
+
+pub(crate) fn lookup(op: &str, coll: &Expr, key: &Expr) -> EvalResult<Option<Expr>> {
+    match coll {
+        Nil => Ok(None),
+        Map(map) => Ok(map.get(&Key::new(key.clone())?).cloned()),
+        Vector(items) => Ok(index(items, key).map(|i| items[i].clone())),
+        Bytes(bytes) => Ok(match key {
+            Int(i) => usize::try_from(*i)
+                .ok()
+                .and_then(|i| bytes.get(i))
+                .map(|byte| Int(*byte as i64)),
+            _ => None,
+        }),
+        _ => Err(EvalError::type_error(op, &[coll.clone(), key.clone()])),
+    }
+}
+
+fn get(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (coll, key, default) = match args {
+        [coll, key] => (coll, key, Nil),
+        [coll, key, default] => (coll, key, default.clone()),
+        _ => return Err(EvalError::ArityMismatch),
+    };
+    Ok(lookup("get", coll, key)?.unwrap_or(default))
+}
+
+fn insert(op: &str, coll: Expr, key: Expr, value: Expr) -> EvalResult<Expr> {
+    match coll {
+        Nil => Ok(Map(im_rc::OrdMap::unit(Key::new(key)?, value))),
+        Map(map) => Ok(Map(map.update(Key::new(key)?, value))),
+        Vector(mut items) => match key {
+            Int(i) if usize::try_from(i).is_ok_and(|i| i == items.len()) => {
+                items.push_back(value);
+                Ok(Vector(items))
+            }
+            Int(i) => match index(&items, &key) {
+                Some(i) => Ok(Vector(items.update(i, value))),
+                None => Err(EvalError::IndexOutOfBounds {
+                    index: i,
+                    len: items.len(),
+                }),
+            },
+            key => Err(EvalError::type_error(op, &[Vector(items), key])),
+        },
+        coll => Err(EvalError::type_error(op, &[coll, key])),
+    }
+}
+
+fn assoc(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [coll, pairs @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    if !pairs.len().is_multiple_of(2) {
+        return Err(EvalError::ArityMismatch);
+    }
+
+    pairs.chunks(2).try_fold(coll.clone(), |coll, pair| {
+        insert("assoc", coll, pair[0].clone(), pair[1].clone())
+    })
+}
+
+fn dissoc(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Nil, ..] => Ok(Nil),
+        [Map(map), keys @ ..] => keys
+            .iter()
+            .try_fold(map.clone(), |map, key| {
+                Ok(map.without(&Key::new(key.clone())?))
+            })
+            .map(Map),
+        _ => Err(EvalError::type_error("dissoc", args)),
+    }
+}
+
+fn keys(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Nil] => Ok(Nil),
+        [Map(map)] => Ok(from_vec(map.keys().map(|key| key.expr().clone()).collect())),
+        _ => Err(EvalError::type_error("keys", args)),
+    }
+}
+
+fn vals(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Nil] => Ok(Nil),
+        [Map(map)] => Ok(from_vec(map.values().cloned().collect())),
+        _ => Err(EvalError::type_error("vals", args)),
+    }
+}
+
+fn contains(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Str(needle)] => Ok(Bool(s.contains(needle.as_str()))),
+        [coll, key] => Ok(Bool(lookup("contains?", coll, key)?.is_some())),
+        _ => Err(EvalError::ArityMismatch),
+    }
+}
+
+fn update(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [coll, key, f, extra @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let mut call_args = vec![lookup("update", coll, key)?.unwrap_or(Nil)];
+    call_args.extend_from_slice(extra);
+    let value = callable("update", f)?.apply(interpreter, call_args)?;
+    insert("update", coll.clone(), key.clone(), value)
+}
+
+fn merge(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let mut result = im_rc::OrdMap::new();
+    for map in args {
+        match map {
+            Nil => {}
+            Map(map) => result.extend(map.clone()),
+            _ => return Err(EvalError::type_error("merge", std::slice::from_ref(map))),
+        }
+    }
+    Ok(Map(result))
+}
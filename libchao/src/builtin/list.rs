@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, EvalMode},
+    interpreter::{EvalError, EvalResult},
+};
+
+use super::insert_builtin;
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "list", EvalMode::Eager, Arity::Any, list);
+    insert_builtin(env, "cons", EvalMode::Eager, Arity::Exact(2), cons);
+    insert_builtin(env, "car", EvalMode::Eager, Arity::Exact(1), car);
+    insert_builtin(env, "first", EvalMode::Eager, Arity::Exact(1), first);
+    insert_builtin(env, "cdr", EvalMode::Eager, Arity::Exact(1), cdr);
+    insert_builtin(env, "rest", EvalMode::Eager, Arity::Exact(1), rest);
+    insert_builtin(env, "nth", EvalMode::Eager, Arity::Exact(2), nth);
+    insert_builtin(env, "length", EvalMode::Eager, Arity::Exact(1), length);
+    insert_builtin(env, "append", EvalMode::Eager, Arity::Any, append);
+    insert_builtin(env, "reverse", EvalMode::Eager, Arity::Exact(1), reverse);
+    insert_builtin(env, "null?", EvalMode::Eager, Arity::Exact(1), is_null);
+    insert_builtin(env, "empty?", EvalMode::Eager, Arity::Exact(1), is_empty);
+}
+
+// `nil` and `()` are the same value, so every list builtin accepts `nil` as
+// the empty list and returns `nil` instead of an empty `List`. Vectors are
+// accepted too and copied out, since they aren't stored contiguously.
+pub(super) fn items<'a>(op: &str, expr: &'a Expr) -> EvalResult<Cow<'a, [Expr]>> {
+    match expr {
+        Nil => Ok(Cow::Borrowed(&[])),
+        List(items) => Ok(Cow::Borrowed(items)),
+        Vector(items) => Ok(Cow::Owned(items.iter().cloned().collect())),
+        _ => Err(EvalError::type_error(op, std::slice::from_ref(expr))),
+    }
+}
+
+pub(super) fn from_vec(items: Vec<Expr>) -> Expr {
+    if items.is_empty() { Nil } else { List(items) }
+}
+
+fn list(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(from_vec(args.to_vec()))
+}
+
+fn cons(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [head, tail] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let tail = items("cons", tail)?;
+    let mut list = Vec::with_capacity(tail.len() + 1);
+    list.push(head.clone());
+    list.extend_from_slice(&tail);
+    Ok(List(list))
+}
+
+fn head(op: &str, args: &[Expr]) -> EvalResult<Expr> {
+    let [list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    Ok(items(op, list)?.first().cloned().unwrap_or(Nil))
+}
+
+fn tail(op: &str, args: &[Expr]) -> EvalResult<Expr> {
+    let [list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    match items(op, list)?.as_ref() {
+        [] => Ok(Nil),
+        [_, tail @ ..] => Ok(from_vec(tail.to_vec())),
+    }
+}
+
+fn first(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    head("first", args)
+}
+
+fn car(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    head("car", args)
+}
+
+fn rest(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    tail("rest", args)
+}
+
+fn cdr(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    tail("cdr", args)
+}
+
+fn nth(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [list, Int(index)] => {
+            let items = items("nth", list)?;
+            Ok(usize::try_from(*index)
+                .ok()
+                .and_then(|index| items.get(index))
+                .cloned()
+                .unwrap_or(Nil))
+        }
+        _ => Err(EvalError::type_error("nth", args)),
+    }
+}
+
+fn length(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Vector(items)] => Ok(Int(items.len() as i64)),
+        [Map(map)] => Ok(Int(map.len() as i64)),
+        [Bytes(bytes)] => Ok(Int(bytes.len() as i64)),
+        [list] => Ok(Int(items("length", list)?.len() as i64)),
+        _ => Err(EvalError::ArityMismatch),
+    }
+}
+
+fn append(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let mut result = vec![];
+    for list in args {
+        result.extend_from_slice(&items("append", list)?);
+    }
+    Ok(from_vec(result))
+}
+
+fn reverse(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    Ok(from_vec(
+        items("reverse", list)?.iter().rev().cloned().collect(),
+    ))
+}
+
+fn is_null(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Bool(
+        matches!(args, [Nil]) || matches!(args, [List(items)] if items.is_empty()),
+    ))
+}
+
+fn is_empty(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    Ok(Bool(items("empty?", list)?.is_empty()))
+}
@@ -0,0 +1,333 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, EvalMode},
+    interpreter::{EvalError, EvalResult},
+    number,
+};
+
+use super::insert_builtin;
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "rem", EvalMode::Eager, Arity::Exact(2), rem);
+    insert_builtin(env, "%", EvalMode::Eager, Arity::Exact(2), rem);
+    insert_builtin(env, "mod", EvalMode::Eager, Arity::Exact(2), modulo);
+    insert_builtin(env, "abs", EvalMode::Eager, Arity::Exact(1), abs);
+    insert_builtin(env, "min", EvalMode::Eager, Arity::AtLeast(1), min);
+    insert_builtin(env, "max", EvalMode::Eager, Arity::AtLeast(1), max);
+    insert_builtin(env, "expt", EvalMode::Eager, Arity::Exact(2), expt);
+    insert_builtin(env, "sqrt", EvalMode::Eager, Arity::Exact(1), sqrt);
+    insert_builtin(env, "exp", EvalMode::Eager, Arity::Exact(1), exp);
+    insert_builtin(env, "log", EvalMode::Eager, Arity::AtLeast(1), log);
+    insert_builtin(env, "sin", EvalMode::Eager, Arity::Exact(1), sin);
+    insert_builtin(env, "cos", EvalMode::Eager, Arity::Exact(1), cos);
+    insert_builtin(env, "tan", EvalMode::Eager, Arity::Exact(1), tan);
+    insert_builtin(env, "asin", EvalMode::Eager, Arity::Exact(1), asin);
+    insert_builtin(env, "acos", EvalMode::Eager, Arity::Exact(1), acos);
+    insert_builtin(env, "atan", EvalMode::Eager, Arity::AtLeast(1), atan);
+    insert_builtin(env, "floor", EvalMode::Eager, Arity::Exact(1), floor);
+    insert_builtin(env, "ceil", EvalMode::Eager, Arity::Exact(1), ceil);
+    insert_builtin(env, "round", EvalMode::Eager, Arity::Exact(1), round);
+    insert_builtin(env, "truncate", EvalMode::Eager, Arity::Exact(1), truncate);
+    insert_builtin(env, "bit-and", EvalMode::Eager, Arity::AtLeast(1), bit_and);
+    insert_builtin(env, "&", EvalMode::Eager, Arity::AtLeast(1), bit_and);
+    insert_builtin(env, "bit-or", EvalMode::Eager, Arity::AtLeast(1), bit_or);
+    insert_builtin(env, "|", EvalMode::Eager, Arity::AtLeast(1), bit_or);
+    insert_builtin(env, "bit-xor", EvalMode::Eager, Arity::AtLeast(1), bit_xor);
+    insert_builtin(env, "^", EvalMode::Eager, Arity::AtLeast(1), bit_xor);
+    insert_builtin(env, "bit-not", EvalMode::Eager, Arity::Exact(1), bit_not);
+    insert_builtin(
+        env,
+        "bit-shift-left",
+        EvalMode::Eager,
+        Arity::Exact(2),
+        shift_left,
+    );
+    insert_builtin(
+        env,
+        "bit-shift-right",
+        EvalMode::Eager,
+        Arity::Exact(2),
+        shift_right,
+    );
+}
+
+fn is_integer(expr: &Expr) -> bool {
+    matches!(expr, Int(_) | BigInt(_))
+}
+
+fn sign(op: &str, n: &Expr) -> EvalResult<Ordering> {
+    number::compare(n, &Int(0)).ok_or_else(|| EvalError::type_error(op, std::slice::from_ref(n)))
+}
+
+// Truncated remainder, which takes the sign of the dividend.
+fn remainder(op: &str, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [a, Int(0)] if is_integer(a) => Err(EvalError::DivisionByZero),
+        [Int(a), Int(b)] => Ok(Int(a.checked_rem(*b).unwrap_or(0))),
+        [a, b] if is_integer(a) && is_integer(b) => {
+            let (a, b) = (number::to_bigint(a).unwrap(), number::to_bigint(b).unwrap());
+            Ok(number::from_bigint(a % b))
+        }
+        [a, b] => match (number::to_f64(a), number::to_f64(b)) {
+            (Some(a), Some(b)) => Ok(Float(a % b)),
+            _ => Err(EvalError::type_error(op, args)),
+        },
+        _ => Err(EvalError::type_error(op, args)),
+    }
+}
+
+fn rem(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    remainder("rem", args)
+}
+
+// Floored modulo, which takes the sign of the divisor.
+fn modulo(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let r = remainder("mod", args)?;
+    let divisor = &args[1];
+    let (r_sign, divisor_sign) = (sign("mod", &r)?, sign("mod", divisor)?);
+    if r_sign != Ordering::Equal && r_sign != divisor_sign {
+        r + divisor.clone()
+    } else {
+        Ok(r)
+    }
+}
+
+fn abs(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match sign("abs", &args[0])? {
+        Ordering::Less => Int(0) - args[0].clone(),
+        _ => Ok(args[0].clone()),
+    }
+}
+
+fn extremum(op: &str, args: &[Expr], keep: Ordering) -> EvalResult<Expr> {
+    let [head, tail @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    if !head.is_number() {
+        return Err(EvalError::type_error(op, args));
+    }
+
+    tail.iter()
+        .try_fold(head.clone(), |acc, x| match number::compare(x, &acc) {
+            Some(ordering) if ordering == keep => Ok(x.clone()),
+            Some(_) => Ok(acc),
+            None => Err(EvalError::type_error(op, &[acc.clone(), x.clone()])),
+        })
+}
+
+fn min(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    extremum("min", args, Ordering::Less)
+}
+
+fn max(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    extremum("max", args, Ordering::Greater)
+}
+
+// Exact powers and left shifts build their result in memory, so results
+// wider than this many bits fail instead of hanging or exhausting memory.
+const MAX_BITS: i64 = 1 << 24;
+
+fn expt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [base, Int(power)] if number::is_exact(base) => {
+            if *power < 0 && *base == Int(0) {
+                return Err(EvalError::DivisionByZero);
+            }
+            let base = number::to_rational(base).unwrap();
+            // The result needs about `bits * power` bits. Powers of -1, 0 and
+            // 1 stay small but still have to fit the exponent `pow` takes.
+            let bits = base.numer().bits().max(base.denom().bits()) as i64;
+            let limit = if bits <= 1 {
+                i32::MAX as i64
+            } else {
+                MAX_BITS / bits
+            };
+            if power.unsigned_abs() > limit as u64 {
+                return Err(EvalError::LimitExceeded {
+                    op: "expt",
+                    value: *power,
+                    limit,
+                });
+            }
+            Ok(number::normalize(base.pow(*power as i32)))
+        }
+        [base, power] => match (number::to_f64(base), number::to_f64(power)) {
+            (Some(base), Some(power)) => Ok(Float(base.powf(power))),
+            _ => Err(EvalError::type_error("expt", args)),
+        },
+        _ => Err(EvalError::type_error("expt", args)),
+    }
+}
+
+fn sqrt(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    if let Some(n) = number::to_bigint(&args[0])
+        && n >= BigInt::from(0)
+    {
+        let root = n.sqrt();
+        if &root * &root == n {
+            return Ok(number::from_bigint(root));
+        }
+    }
+    float_fn("sqrt", args, f64::sqrt)
+}
+
+fn float_fn(op: &str, args: &[Expr], f: fn(f64) -> f64) -> EvalResult<Expr> {
+    match args {
+        [n] => number::to_f64(n)
+            .map(|n| Float(f(n)))
+            .ok_or_else(|| EvalError::type_error(op, args)),
+        _ => Err(EvalError::type_error(op, args)),
+    }
+}
+
+fn exp(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("exp", args, f64::exp)
+}
+
+fn log(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [_] => float_fn("log", args, f64::ln),
+        [n, base] => match (number::to_f64(n), number::to_f64(base)) {
+            (Some(n), Some(base)) => Ok(Float(n.log(base))),
+            _ => Err(EvalError::type_error("log", args)),
+        },
+        _ => Err(EvalError::ArityMismatch),
+    }
+}
+
+fn sin(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("sin", args, f64::sin)
+}
+
+fn cos(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("cos", args, f64::cos)
+}
+
+fn tan(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("tan", args, f64::tan)
+}
+
+fn asin(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("asin", args, f64::asin)
+}
+
+fn acos(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    float_fn("acos", args, f64::acos)
+}
+
+fn atan(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [_] => float_fn("atan", args, f64::atan),
+        [y, x] => match (number::to_f64(y), number::to_f64(x)) {
+            (Some(y), Some(x)) => Ok(Float(y.atan2(x))),
+            _ => Err(EvalError::type_error("atan", args)),
+        },
+        _ => Err(EvalError::ArityMismatch),
+    }
+}
+
+fn rounding(
+    op: &str,
+    args: &[Expr],
+    exact: fn(&BigRational) -> BigRational,
+    inexact: fn(f64) -> f64,
+) -> EvalResult<Expr> {
+    match args {
+        [n] if is_integer(n) => Ok(n.clone()),
+        [Ratio(n)] => Ok(number::normalize(exact(n))),
+        [Float(n)] => Ok(Float(inexact(*n))),
+        _ => Err(EvalError::type_error(op, args)),
+    }
+}
+
+fn floor(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    rounding("floor", args, BigRational::floor, f64::floor)
+}
+
+fn ceil(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    rounding("ceil", args, BigRational::ceil, f64::ceil)
+}
+
+fn round(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    rounding("round", args, BigRational::round, f64::round)
+}
+
+fn truncate(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    rounding("truncate", args, BigRational::trunc, f64::trunc)
+}
+
+fn bitwise(
+    op: &str,
+    args: &[Expr],
+    int: fn(i64, i64) -> i64,
+    big: fn(BigInt, BigInt) -> BigInt,
+) -> EvalResult<Expr> {
+    let [head, tail @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+    if !is_integer(head) {
+        return Err(EvalError::type_error(op, args));
+    }
+
+    tail.iter()
+        .try_fold(head.clone(), |acc, x| match (&acc, x) {
+            (Int(a), Int(b)) => Ok(Int(int(*a, *b))),
+            _ => match (number::to_bigint(&acc), number::to_bigint(x)) {
+                (Some(a), Some(b)) => Ok(number::from_bigint(big(a, b))),
+                _ => Err(EvalError::type_error(op, &[acc.clone(), x.clone()])),
+            },
+        })
+}
+
+fn bit_and(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    bitwise("bit-and", args, |a, b| a & b, |a, b| a & b)
+}
+
+fn bit_or(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    bitwise("bit-or", args, |a, b| a | b, |a, b| a | b)
+}
+
+fn bit_xor(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    bitwise("bit-xor", args, |a, b| a ^ b, |a, b| a ^ b)
+}
+
+fn bit_not(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Int(n)] => Ok(Int(!n)),
+        [BigInt(n)] => Ok(number::from_bigint(!n)),
+        _ => Err(EvalError::type_error("bit-not", args)),
+    }
+}
+
+fn shift_left(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [n, Int(shift @ 0..)] if is_integer(n) => {
+            if *shift > MAX_BITS {
+                return Err(EvalError::LimitExceeded {
+                    op: "bit-shift-left",
+                    value: *shift,
+                    limit: MAX_BITS,
+                });
+            }
+            Ok(number::from_bigint(
+                number::to_bigint(n).unwrap() << *shift as usize,
+            ))
+        }
+        _ => Err(EvalError::type_error("bit-shift-left", args)),
+    }
+}
+
+fn shift_right(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Int(n), Int(shift @ 0..)] => Ok(Int(n >> (*shift).min(63))),
+        [BigInt(n), Int(shift @ 0..)] => Ok(number::from_bigint(n >> *shift as usize)),
+        _ => Err(EvalError::type_error("bit-shift-right", args)),
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, Callable, EvalMode},
+    interpreter::{EvalError, EvalResult, apply_keyword},
+};
+
+use super::{
+    insert_builtin,
+    list::{from_vec, items},
+};
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "apply", EvalMode::Eager, Arity::AtLeast(2), apply);
+    insert_builtin(env, "map", EvalMode::Eager, Arity::AtLeast(2), map);
+    insert_builtin(env, "filter", EvalMode::Eager, Arity::Exact(2), filter);
+    insert_builtin(env, "reduce", EvalMode::Eager, Arity::AtLeast(2), reduce);
+    insert_builtin(
+        env,
+        "for-each",
+        EvalMode::Eager,
+        Arity::AtLeast(2),
+        for_each,
+    );
+    insert_builtin(env, "sort", EvalMode::Eager, Arity::AtLeast(1), sort);
+    insert_builtin(env, "sort-by", EvalMode::Eager, Arity::Exact(2), sort_by);
+    insert_builtin(env, "any?", EvalMode::Eager, Arity::Exact(2), any);
+    insert_builtin(env, "every?", EvalMode::Eager, Arity::Exact(2), every);
+}
+
+// Keywords can be passed wherever a function is expected and look
+// themselves up in their argument.
+pub(super) enum Function<'a> {
+    Callable(&'a Callable),
+    Keyword(&'a Expr),
+}
+
+impl Function<'_> {
+    pub(super) fn apply(&self, interpreter: &mut Interpreter, args: Vec<Expr>) -> EvalResult<Expr> {
+        match self {
+            Function::Callable(callable) => interpreter.apply(callable, args),
+            Function::Keyword(keyword) => apply_keyword(keyword, &args),
+        }
+    }
+}
+
+pub(super) fn callable<'a>(op: &str, expr: &'a Expr) -> EvalResult<Function<'a>> {
+    match expr {
+        Callable(callable) => Ok(Function::Callable(callable)),
+        Keyword(_) => Ok(Function::Keyword(expr)),
+        _ => Err(EvalError::type_error(op, std::slice::from_ref(expr))),
+    }
+}
+
+fn apply(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, init @ .., last] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let mut call_args = init.to_vec();
+    call_args.extend_from_slice(&items("apply", last)?);
+    callable("apply", f)?.apply(interpreter, call_args)
+}
+
+fn zip_lists(op: &str, lists: &[Expr]) -> EvalResult<Vec<Vec<Expr>>> {
+    let lists = lists
+        .iter()
+        .map(|list| items(op, list))
+        .collect::<EvalResult<Vec<_>>>()?;
+    let len = lists.iter().map(|list| list.len()).min().unwrap_or(0);
+
+    Ok((0..len)
+        .map(|i| lists.iter().map(|list| list[i].clone()).collect())
+        .collect())
+}
+
+fn map(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, lists @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("map", f)?;
+    zip_lists("map", lists)?
+        .into_iter()
+        .map(|row| f.apply(interpreter, row))
+        .collect::<EvalResult<Vec<_>>>()
+        .map(from_vec)
+}
+
+fn for_each(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, lists @ ..] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("for-each", f)?;
+    for row in zip_lists("for-each", lists)? {
+        f.apply(interpreter, row)?;
+    }
+    Ok(Nil)
+}
+
+fn filter(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("filter", f)?;
+    let mut result = vec![];
+    for item in items("filter", list)?.iter() {
+        if f.apply(interpreter, vec![item.clone()])?.is_truthy() {
+            result.push(item.clone());
+        }
+    }
+    Ok(from_vec(result))
+}
+
+fn reduce(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (f, init, list) = match args {
+        [f, list] => (f, None, items("reduce", list)?),
+        [f, init, list] => (f, Some(init), items("reduce", list)?),
+        _ => return Err(EvalError::ArityMismatch),
+    };
+    let (init, list) = match (init, list.as_ref()) {
+        (Some(init), list) => (init.clone(), list),
+        (None, []) => return Ok(Nil),
+        (None, [head, tail @ ..]) => (head.clone(), tail),
+    };
+
+    let f = callable("reduce", f)?;
+    list.iter().try_fold(init, |acc, item| {
+        f.apply(interpreter, vec![acc, item.clone()])
+    })
+}
+
+fn any(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("any?", f)?;
+    for item in items("any?", list)?.iter() {
+        if f.apply(interpreter, vec![item.clone()])?.is_truthy() {
+            return Ok(Bool(true));
+        }
+    }
+    Ok(Bool(false))
+}
+
+fn every(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("every?", f)?;
+    for item in items("every?", list)?.iter() {
+        if f.apply(interpreter, vec![item.clone()])?.is_falsy() {
+            return Ok(Bool(false));
+        }
+    }
+    Ok(Bool(true))
+}
+
+fn less_than(a: &Expr, b: &Expr) -> EvalResult<bool> {
+    match a.partial_cmp(b) {
+        Some(ordering) => Ok(ordering.is_lt()),
+        None => Err(EvalError::type_error("sort", &[a.clone(), b.clone()])),
+    }
+}
+
+// A stable merge sort, since `slice::sort_by` may panic when a user supplied
+// comparison is not a total order and cannot propagate errors.
+fn merge_sort<T: Clone>(
+    items: Vec<T>,
+    less: &mut impl FnMut(&T, &T) -> EvalResult<bool>,
+) -> EvalResult<Vec<T>> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+
+    let mut left = items;
+    let right = left.split_off(left.len() / 2);
+    let left = merge_sort(left, less)?;
+    let right = merge_sort(right, less)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if less(&right[j], &left[i])? {
+            merged.push(right[j].clone());
+            j += 1;
+        } else {
+            merged.push(left[i].clone());
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    Ok(merged)
+}
+
+fn sort(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let sorted = match args {
+        [list] => merge_sort(items("sort", list)?.to_vec(), &mut |a, b| less_than(a, b))?,
+        [f, list] => {
+            let f = callable("sort", f)?;
+            merge_sort(items("sort", list)?.to_vec(), &mut |a, b| {
+                Ok(f.apply(interpreter, vec![a.clone(), b.clone()])?
+                    .is_truthy())
+            })?
+        }
+        _ => return Err(EvalError::ArityMismatch),
+    };
+    Ok(from_vec(sorted))
+}
+
+fn sort_by(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [f, list] = args else {
+        return Err(EvalError::ArityMismatch);
+    };
+
+    let f = callable("sort-by", f)?;
+    let keyed = items("sort-by", list)?
+        .iter()
+        .map(|item| Ok((f.apply(interpreter, vec![item.clone()])?, item.clone())))
+        .collect::<EvalResult<Vec<_>>>()?;
+
+    let sorted = merge_sort(keyed, &mut |(a, _), (b, _)| less_than(a, b))?;
+    Ok(from_vec(sorted.into_iter().map(|(_, item)| item).collect()))
+}
@@ -0,0 +1,328 @@
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, EvalMode},
+    interpreter::{EvalError, EvalResult},
+    parser,
+};
+
+use super::{
+    insert_builtin,
+    list::{from_vec, items},
+};
+
+pub fn load(env: &mut Env) {
+    insert_builtin(env, "str", EvalMode::Eager, Arity::Any, str);
+    insert_builtin(env, "print", EvalMode::Eager, Arity::Any, print);
+    insert_builtin(env, "println", EvalMode::Eager, Arity::Any, println);
+    insert_builtin(
+        env,
+        "string-length",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        string_length,
+    );
+    insert_builtin(
+        env,
+        "substring",
+        EvalMode::Eager,
+        Arity::AtLeast(2),
+        substring,
+    );
+    insert_builtin(env, "char-at", EvalMode::Eager, Arity::Exact(2), char_at);
+    insert_builtin(
+        env,
+        "char->int",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        char_to_int,
+    );
+    insert_builtin(
+        env,
+        "int->char",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        int_to_char,
+    );
+    insert_builtin(
+        env,
+        "string->list",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        string_to_list,
+    );
+    insert_builtin(
+        env,
+        "list->string",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        list_to_string,
+    );
+    insert_builtin(env, "split", EvalMode::Eager, Arity::Exact(2), split);
+    insert_builtin(env, "join", EvalMode::Eager, Arity::AtLeast(1), join);
+    insert_builtin(env, "trim", EvalMode::Eager, Arity::Exact(1), trim);
+    insert_builtin(env, "upcase", EvalMode::Eager, Arity::Exact(1), upcase);
+    insert_builtin(env, "downcase", EvalMode::Eager, Arity::Exact(1), downcase);
+    insert_builtin(
+        env,
+        "starts-with?",
+        EvalMode::Eager,
+        Arity::Exact(2),
+        starts_with,
+    );
+    insert_builtin(
+        env,
+        "ends-with?",
+        EvalMode::Eager,
+        Arity::Exact(2),
+        ends_with,
+    );
+    insert_builtin(env, "replace", EvalMode::Eager, Arity::Exact(3), replace);
+    insert_builtin(
+        env,
+        "string->number",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        string_to_number,
+    );
+    insert_builtin(
+        env,
+        "number->string",
+        EvalMode::Eager,
+        Arity::AtLeast(1),
+        number_to_string,
+    );
+    insert_builtin(env, "format", EvalMode::Eager, Arity::AtLeast(1), format);
+}
+
+fn str(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Str(args.iter().map(Expr::to_plain_string).collect()))
+}
+
+fn print(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    if let Str(s) = str(interpreter, args)? {
+        print!("{}", s);
+    }
+    Ok(Nil)
+}
+
+fn println(interpreter: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    if let Str(s) = str(interpreter, args)? {
+        println!("{}", s);
+    }
+    Ok(Nil)
+}
+
+fn string_length(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(Int(s.chars().count() as i64)),
+        _ => Err(EvalError::type_error("string-length", args)),
+    }
+}
+
+pub(super) fn char_index(index: i64, len: usize) -> EvalResult<usize> {
+    usize::try_from(index)
+        .ok()
+        .filter(|i| *i <= len)
+        .ok_or(EvalError::IndexOutOfBounds { index, len })
+}
+
+fn substring(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (s, start, end) = match args {
+        [Str(s), Int(start)] => (s, *start, None),
+        [Str(s), Int(start), Int(end)] => (s, *start, Some(*end)),
+        _ => return Err(EvalError::type_error("substring", args)),
+    };
+
+    let len = s.chars().count();
+    let start = char_index(start, len)?;
+    let end = match end {
+        Some(end) => char_index(end, len)?,
+        None => len,
+    };
+    if end < start {
+        return Err(EvalError::IndexOutOfBounds {
+            index: end as i64,
+            len,
+        });
+    }
+
+    Ok(Str(s.chars().skip(start).take(end - start).collect()))
+}
+
+fn char_at(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Int(index)] => usize::try_from(*index)
+            .ok()
+            .and_then(|i| s.chars().nth(i))
+            .map(Char)
+            .ok_or(EvalError::IndexOutOfBounds {
+                index: *index,
+                len: s.chars().count(),
+            }),
+        _ => Err(EvalError::type_error("char-at", args)),
+    }
+}
+
+fn char_to_int(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Char(c)] => Ok(Int(*c as i64)),
+        _ => Err(EvalError::type_error("char->int", args)),
+    }
+}
+
+fn int_to_char(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Int(n)] => u32::try_from(*n)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Char)
+            .ok_or_else(|| EvalError::type_error("int->char", args)),
+        _ => Err(EvalError::type_error("int->char", args)),
+    }
+}
+
+fn string_to_list(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(from_vec(s.chars().map(Char).collect())),
+        _ => Err(EvalError::type_error("string->list", args)),
+    }
+}
+
+fn list_to_string(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    items("list->string", &args[0])?
+        .iter()
+        .map(|item| match item {
+            Char(c) => Ok(*c),
+            _ => Err(EvalError::type_error("list->string", args)),
+        })
+        .collect::<EvalResult<String>>()
+        .map(Str)
+}
+
+fn split(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Str(sep)] if sep.is_empty() => {
+            Ok(from_vec(s.chars().map(|c| Str(c.to_string())).collect()))
+        }
+        [Str(s), Str(sep)] => Ok(from_vec(
+            s.split(sep.as_str())
+                .map(|part| Str(part.to_string()))
+                .collect(),
+        )),
+        _ => Err(EvalError::type_error("split", args)),
+    }
+}
+
+fn join(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let (list, sep) = match args {
+        [list] => (list, ""),
+        [list, Str(sep)] => (list, sep.as_str()),
+        _ => return Err(EvalError::type_error("join", args)),
+    };
+
+    let parts: Vec<String> = items("join", list)?
+        .iter()
+        .map(Expr::to_plain_string)
+        .collect();
+    Ok(Str(parts.join(sep)))
+}
+
+fn trim(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(Str(s.trim().to_string())),
+        _ => Err(EvalError::type_error("trim", args)),
+    }
+}
+
+fn upcase(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(Str(s.to_uppercase())),
+        _ => Err(EvalError::type_error("upcase", args)),
+    }
+}
+
+fn downcase(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => Ok(Str(s.to_lowercase())),
+        _ => Err(EvalError::type_error("downcase", args)),
+    }
+}
+
+fn starts_with(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Str(prefix)] => Ok(Bool(s.starts_with(prefix.as_str()))),
+        _ => Err(EvalError::type_error("starts-with?", args)),
+    }
+}
+
+fn ends_with(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Str(suffix)] => Ok(Bool(s.ends_with(suffix.as_str()))),
+        _ => Err(EvalError::type_error("ends-with?", args)),
+    }
+}
+
+fn replace(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s), Str(from), Str(to)] => Ok(Str(s.replace(from.as_str(), to))),
+        _ => Err(EvalError::type_error("replace", args)),
+    }
+}
+
+fn string_to_number(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => match parser::parse(s) {
+            Ok(n) if n.is_number() => Ok(n),
+            _ => Ok(Nil),
+        },
+        _ => Err(EvalError::type_error("string->number", args)),
+    }
+}
+
+fn number_to_string(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [n] if n.is_number() => Ok(Str(n.to_plain_string())),
+        [Int(n), Int(radix @ 2..=36)] => {
+            Ok(Str(num_bigint::BigInt::from(*n).to_str_radix(*radix as u32)))
+        }
+        [BigInt(n), Int(radix @ 2..=36)] => Ok(Str(n.to_str_radix(*radix as u32))),
+        _ => Err(EvalError::type_error("number->string", args)),
+    }
+}
+
+fn format(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    let [Str(template), values @ ..] = args else {
+        return Err(EvalError::type_error("format", args));
+    };
+
+    let mut values = values.iter();
+    let mut next =
+        |repr: fn(&Expr) -> String| values.next().map(repr).ok_or(EvalError::ArityMismatch);
+
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('~', Some('a')) | ('{', Some('}')) => {
+                chars.next();
+                result.push_str(&next(Expr::to_plain_string)?);
+            }
+            ('~', Some('s')) => {
+                chars.next();
+                result.push_str(&next(|value| format!("{:?}", value))?);
+            }
+            ('~', Some('~')) | ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    if values.next().is_some() {
+        return Err(EvalError::ArityMismatch);
+    }
+    Ok(Str(result))
+}
@@ -0,0 +1,148 @@
+use crate::{
+    Interpreter,
+    env::Env,
+    expr::Expr::{self, *},
+    functions::{Arity, Callable, EvalMode},
+    interpreter::{EvalError, EvalResult},
+    number,
+};
+
+use super::insert_builtin;
+
+macro_rules! predicate {
+    ($env:expr, $name:expr, $pattern:pat) => {
+        insert_builtin($env, $name, EvalMode::Eager, Arity::Exact(1), |_, args| {
+            Ok(Bool(matches!(args, [$pattern])))
+        })
+    };
+}
+
+pub fn load(env: &mut Env) {
+    predicate!(env, "nil?", Nil);
+    predicate!(env, "bool?", Bool(_));
+    predicate!(env, "int?", Int(_) | BigInt(_));
+    predicate!(env, "ratio?", Ratio(_));
+    predicate!(env, "float?", Float(_));
+    predicate!(env, "number?", Int(_) | BigInt(_) | Ratio(_) | Float(_));
+    predicate!(env, "exact?", Int(_) | BigInt(_) | Ratio(_));
+    predicate!(env, "inexact?", Float(_));
+    predicate!(env, "string?", Str(_));
+    predicate!(env, "char?", Char(_));
+    predicate!(env, "bytes?", Bytes(_));
+    predicate!(env, "symbol?", Symbol(_));
+    predicate!(env, "keyword?", Keyword(_));
+    predicate!(env, "list?", List(_) | Nil);
+    predicate!(env, "vector?", Vector(_));
+    predicate!(env, "map?", Map(_));
+    predicate!(
+        env,
+        "fn?",
+        Callable(Callable::Builtin(_) | Callable::SpecialForm(_) | Callable::Lambda(_))
+    );
+    predicate!(env, "macro?", Callable(Callable::Macro(_)));
+
+    insert_builtin(env, "type-of", EvalMode::Eager, Arity::Exact(1), type_of);
+    insert_builtin(
+        env,
+        "symbol->string",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        symbol_to_string,
+    );
+    insert_builtin(env, "keyword", EvalMode::Eager, Arity::Exact(1), keyword);
+    insert_builtin(env, "name", EvalMode::Eager, Arity::Exact(1), name);
+    insert_builtin(env, "int", EvalMode::Eager, Arity::Exact(1), int);
+    insert_builtin(env, "float", EvalMode::Eager, Arity::Exact(1), float);
+    insert_builtin(
+        env,
+        "exact->inexact",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        exact_to_inexact,
+    );
+    insert_builtin(
+        env,
+        "inexact->exact",
+        EvalMode::Eager,
+        Arity::Exact(1),
+        inexact_to_exact,
+    );
+    insert_builtin(env, "boolean", EvalMode::Eager, Arity::Exact(1), boolean);
+}
+
+fn type_of(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Symbol(args[0].type_name().to_string()))
+}
+
+fn symbol_to_string(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Symbol(s)] => Ok(Str(s.clone())),
+        _ => Err(EvalError::type_error("symbol->string", args)),
+    }
+}
+
+fn keyword(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(name) | Symbol(name) | Keyword(name)] => Ok(Keyword(name.clone())),
+        _ => Err(EvalError::type_error("keyword", args)),
+    }
+}
+
+fn name(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(name) | Symbol(name) | Keyword(name)] => Ok(Str(name.clone())),
+        _ => Err(EvalError::type_error("name", args)),
+    }
+}
+
+fn int(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Int(_) | BigInt(_)] => Ok(args[0].clone()),
+        [Ratio(n)] => Ok(number::from_bigint(n.to_integer())),
+        [Float(f)] => number::from_f64(f.trunc()).ok_or_else(|| EvalError::type_error("int", args)),
+        [Bool(b)] => Ok(Int(*b as i64)),
+        [Str(s)] => s
+            .trim()
+            .parse()
+            .map(number::from_bigint)
+            .map_err(|_| EvalError::type_error("int", args)),
+        _ => Err(EvalError::type_error("int", args)),
+    }
+}
+
+fn float(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Str(s)] => s
+            .trim()
+            .parse()
+            .map(Float)
+            .map_err(|_| EvalError::type_error("float", args)),
+        [n] => number::to_f64(n)
+            .map(Float)
+            .ok_or_else(|| EvalError::type_error("float", args)),
+        _ => Err(EvalError::type_error("float", args)),
+    }
+}
+
+fn exact_to_inexact(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [n] => number::to_f64(n)
+            .map(Float)
+            .ok_or_else(|| EvalError::type_error("exact->inexact", args)),
+        _ => Err(EvalError::type_error("exact->inexact", args)),
+    }
+}
+
+fn inexact_to_exact(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    match args {
+        [Float(f)] => {
+            number::from_f64(*f).ok_or_else(|| EvalError::type_error("inexact->exact", args))
+        }
+        [n] if number::is_exact(n) => Ok(n.clone()),
+        _ => Err(EvalError::type_error("inexact->exact", args)),
+    }
+}
+
+fn boolean(_: &mut Interpreter, args: &[Expr]) -> EvalResult<Expr> {
+    Ok(Bool(args[0].is_truthy()))
+}
@@ -1,5 +1,7 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+use itertools::Itertools;
+
 use crate::expr::Expr;
 
 #[derive(Debug, Clone)]
@@ -74,6 +76,46 @@ impl Env {
             .and_then(|e| e.get(key))
     }
 
+    pub fn contains(&self, key: &str) -> bool {
+        if self.inner.borrow().values.contains_key(key) {
+            return true;
+        }
+
+        self.inner
+            .borrow()
+            .enclosing
+            .as_ref()
+            .is_some_and(|e| e.contains(key))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let inner = self.inner.borrow();
+        let mut names: Vec<String> = inner.values.keys().cloned().sorted().collect();
+
+        if let Some(enclosing) = &inner.enclosing {
+            names.extend(enclosing.names());
+        }
+
+        names
+    }
+
+    // Names made only of operator characters are not suggested for words,
+    // and every suggestion keeps at least one character of the key, since
+    // any two one-character names are a single edit apart.
+    pub fn suggest(&self, key: &str) -> Option<String> {
+        let length = key.chars().count();
+        let max_distance = (length / 3).max(1);
+        let is_word = key.chars().any(char::is_alphanumeric);
+
+        self.names()
+            .into_iter()
+            .filter(|name| !is_word || name.chars().any(char::is_alphanumeric))
+            .map(|name| (edit_distance(key, &name), name))
+            .filter(|(distance, _)| *distance <= max_distance && *distance < length)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+    }
+
     pub fn child(&self) -> Self {
         Self {
             inner: Rc::new(RefCell::new(EnvInner {
@@ -94,6 +136,24 @@ impl Env {
     }
 }
 
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
 impl Default for Env {
     fn default() -> Self {
         Self::new()
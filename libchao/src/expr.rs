@@ -7,20 +7,162 @@ use itertools::Itertools;
 
 use crate::functions::Callable;
 use crate::interpreter::{EvalError, EvalResult};
+use crate::number;
 
 #[derive(Clone, PartialEq)]
 pub enum Expr {
     Nil,
     Bool(bool),
     Int(i64),
+    BigInt(num_bigint::BigInt),
+    Ratio(num_rational::BigRational),
     Float(f64),
     Str(String),
+    Char(char),
+    Bytes(Vec<u8>),
     Symbol(String),
+    Keyword(String),
     Quote(Box<Expr>),
     QuasiQuote(Box<Expr>),
     Unquote(Box<Expr>),
+    UnquoteSplicing(Box<Expr>),
     Callable(Callable),
     List(Vec<Expr>),
+    Vector(im_rc::Vector<Expr>),
+    Map(im_rc::OrdMap<Key, Expr>),
+    Error(Box<EvalError>),
+}
+
+pub const CHAR_NAMES: &[(&str, char)] = &[
+    ("space", ' '),
+    ("newline", '\n'),
+    ("tab", '\t'),
+    ("return", '\r'),
+    ("nul", '\0'),
+];
+
+// Map keys are ordered by a total order over everything that can be compared
+// structurally, which rules out callables and errors.
+#[derive(Clone)]
+pub struct Key(Expr);
+
+impl Key {
+    pub fn new(expr: Expr) -> EvalResult<Self> {
+        if is_key(&expr) {
+            Ok(Key(expr))
+        } else {
+            Err(EvalError::InvalidMapKey(Box::new(expr)))
+        }
+    }
+
+    pub fn expr(&self) -> &Expr {
+        &self.0
+    }
+
+    pub fn into_expr(self) -> Expr {
+        self.0
+    }
+}
+
+fn is_key(expr: &Expr) -> bool {
+    match expr {
+        Callable(_) | Error(_) => false,
+        Quote(inner) | QuasiQuote(inner) | Unquote(inner) | UnquoteSplicing(inner) => is_key(inner),
+        List(items) => items.iter().all(is_key),
+        Vector(items) => items.iter().all(is_key),
+        Map(map) => map.values().all(is_key),
+        _ => true,
+    }
+}
+
+fn rank(expr: &Expr) -> u8 {
+    match expr {
+        Nil => 0,
+        Bool(_) => 1,
+        Int(_) | BigInt(_) | Ratio(_) => 2,
+        Float(_) => 3,
+        Str(_) => 4,
+        Char(_) => 5,
+        Bytes(_) => 6,
+        Symbol(_) => 7,
+        Keyword(_) => 8,
+        Quote(_) => 9,
+        QuasiQuote(_) => 10,
+        Unquote(_) => 11,
+        UnquoteSplicing(_) => 12,
+        List(_) => 13,
+        Vector(_) => 14,
+        Map(_) => 15,
+        Callable(_) => 16,
+        Error(_) => 17,
+    }
+}
+
+fn total_cmp(a: &Expr, b: &Expr) -> Ordering {
+    match (a, b) {
+        (Bool(a), Bool(b)) => a.cmp(b),
+        (Char(a), Char(b)) => a.cmp(b),
+        (Bytes(a), Bytes(b)) => a.cmp(b),
+        (Float(a), Float(b)) => a.total_cmp(b),
+        (Str(a), Str(b)) | (Symbol(a), Symbol(b)) | (Keyword(a), Keyword(b)) => a.cmp(b),
+        (Quote(a), Quote(b))
+        | (QuasiQuote(a), QuasiQuote(b))
+        | (Unquote(a), Unquote(b))
+        | (UnquoteSplicing(a), UnquoteSplicing(b)) => total_cmp(a, b),
+        (List(a), List(b)) => total_cmp_all(a.iter(), b.iter()),
+        (Vector(a), Vector(b)) => total_cmp_all(a.iter(), b.iter()),
+        (Map(a), Map(b)) => total_cmp_all(
+            a.iter().flat_map(|(k, v)| [k.expr(), v]),
+            b.iter().flat_map(|(k, v)| [k.expr(), v]),
+        ),
+        (a, b) if number::is_exact(a) && number::is_exact(b) => {
+            number::compare(a, b).unwrap_or(Ordering::Equal)
+        }
+        (a, b) => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn total_cmp_all<'a>(
+    mut a: impl Iterator<Item = &'a Expr>,
+    mut b: impl Iterator<Item = &'a Expr>,
+) -> Ordering {
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => match total_cmp(x, y) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        total_cmp(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
 }
 
 impl Expr {
@@ -31,6 +173,43 @@ impl Expr {
     pub fn is_falsy(&self) -> bool {
         matches!(self, Nil | Bool(false))
     }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Int(_) | BigInt(_) | Ratio(_) | Float(_))
+    }
+
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            Str(s) => s.clone(),
+            Char(c) => c.to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Nil => "nil",
+            Bool(_) => "bool",
+            Int(_) | BigInt(_) => "int",
+            Ratio(_) => "ratio",
+            Float(_) => "float",
+            Str(_) => "string",
+            Char(_) => "char",
+            Bytes(_) => "bytes",
+            Symbol(_) => "symbol",
+            Keyword(_) => "keyword",
+            Quote(_) => "quote",
+            QuasiQuote(_) => "quasiquote",
+            Unquote(_) => "unquote",
+            UnquoteSplicing(_) => "unquote-splicing",
+            Callable(Callable::Macro(_)) => "macro",
+            Callable(_) => "fn",
+            List(_) => "list",
+            Vector(_) => "vector",
+            Map(_) => "map",
+            Error(_) => "error",
+        }
+    }
 }
 
 impl PartialOrd for Expr {
@@ -38,15 +217,16 @@ impl PartialOrd for Expr {
         match (self, other) {
             (Nil, Nil) => Some(Ordering::Equal),
             (Bool(a), Bool(b)) => PartialOrd::partial_cmp(a, b),
-            (Int(a), Int(b)) => PartialOrd::partial_cmp(a, b),
-            (Int(a), Float(b)) => PartialOrd::partial_cmp(&(*a as f64), b),
-            (Float(a), Float(b)) => PartialOrd::partial_cmp(a, b),
-            (Float(a), Int(b)) => PartialOrd::partial_cmp(a, &(*b as f64)),
+            (a, b) if a.is_number() && b.is_number() => number::compare(a, b),
             (Str(a), Str(b)) => PartialOrd::partial_cmp(a, b),
+            (Char(a), Char(b)) => PartialOrd::partial_cmp(a, b),
+            (Bytes(a), Bytes(b)) => PartialOrd::partial_cmp(a, b),
             (Symbol(a), Symbol(b)) => PartialOrd::partial_cmp(a, b),
+            (Keyword(a), Keyword(b)) => PartialOrd::partial_cmp(a, b),
             (Quote(a), Quote(b)) => PartialOrd::partial_cmp(a, b),
             (QuasiQuote(a), QuasiQuote(b)) => PartialOrd::partial_cmp(a, b),
             (Unquote(a), Unquote(b)) => PartialOrd::partial_cmp(a, b),
+            (UnquoteSplicing(a), UnquoteSplicing(b)) => PartialOrd::partial_cmp(a, b),
             _ => None,
         }
     }
@@ -54,20 +234,53 @@ impl PartialOrd for Expr {
 
 use Expr::*;
 
+fn escape_byte(byte: u8) -> String {
+    match byte {
+        b'"' => "\\\"".to_string(),
+        b'\\' => "\\\\".to_string(),
+        b'\n' => "\\n".to_string(),
+        b'\r' => "\\r".to_string(),
+        b'\t' => "\\t".to_string(),
+        b' '..=b'~' => char::from(byte).to_string(),
+        _ => format!("\\x{:02x}", byte),
+    }
+}
+
 impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Nil => write!(f, "nil"),
             Bool(x) => write!(f, "{:?}", x),
             Int(x) => write!(f, "{:?}", x),
+            BigInt(x) => write!(f, "{}", x),
+            Ratio(x) => write!(f, "{}", x),
             Float(x) => write!(f, "{:?}", x),
             Str(x) => write!(f, "{:?}", x),
+            Char(x) => match CHAR_NAMES.iter().find(|(_, c)| c == x) {
+                Some((name, _)) => write!(f, "#\\{}", name),
+                None if x.is_control() || x.is_whitespace() => {
+                    write!(f, "#\\x{:x}", *x as u32)
+                }
+                None => write!(f, "#\\{}", x),
+            },
+            Bytes(xs) => write!(f, "#b\"{}\"", xs.iter().map(|x| escape_byte(*x)).join("")),
             Symbol(x) => write!(f, "{}", x),
+            Keyword(x) => write!(f, ":{}", x),
             Quote(x) => write!(f, "'{:?}", x),
             QuasiQuote(x) => write!(f, "`{:?}", x),
             Unquote(x) => write!(f, ",{:?}", x),
+            UnquoteSplicing(x) => write!(f, ",@{:?}", x),
             Callable(_) => write!(f, "<callable>"),
             List(xs) => write!(f, "({})", xs.iter().map(|x| format!("{:?}", x)).join(" ")),
+            Vector(xs) => write!(f, "[{}]", xs.iter().map(|x| format!("{:?}", x)).join(" ")),
+            Map(map) => write!(
+                f,
+                "{{{}}}",
+                map.iter()
+                    .map(|(k, v)| format!("{:?} {:?}", k, v))
+                    .join(" ")
+            ),
+            Error(err) => write!(f, "<error {}: {}>", err.kind_name(), err),
         }
     }
 }
@@ -77,15 +290,27 @@ impl fmt::Display for Expr {
         match self {
             Nil => write!(f, "{}", format!("{:?}", self).cyan()),
             Bool(_) => write!(f, "{}", format!("{:?}", self).green()),
-            Int(_) => write!(f, "{}", format!("{:?}", self).blue()),
-            Float(_) => write!(f, "{}", format!("{:?}", self).blue()),
-            Str(_) => write!(f, "{}", format!("{:?}", self).yellow()),
+            Int(_) | BigInt(_) | Ratio(_) | Float(_) => {
+                write!(f, "{}", format!("{:?}", self).blue())
+            }
+            Str(_) | Char(_) | Bytes(_) => write!(f, "{}", format!("{:?}", self).yellow()),
             Symbol(_) => write!(f, "{}", format!("{:?}", self).bright_white()),
+            Keyword(_) => write!(f, "{}", format!("{:?}", self).cyan()),
             Quote(x) => write!(f, "'{}", x),
             QuasiQuote(x) => write!(f, "`{}", x),
             Unquote(x) => write!(f, ",{}", x),
+            UnquoteSplicing(x) => write!(f, ",@{}", x),
             Callable(_) => write!(f, "{}", format!("{:?}", self).magenta()),
             List(xs) => write!(f, "({})", xs.iter().map(|x| format!("{}", x)).join(" ")),
+            Vector(xs) => write!(f, "[{}]", xs.iter().map(|x| format!("{}", x)).join(" ")),
+            Map(map) => write!(
+                f,
+                "{{{}}}",
+                map.iter()
+                    .map(|(k, v)| format!("{} {}", k.expr(), v))
+                    .join(" ")
+            ),
+            Error(_) => write!(f, "{}", format!("{:?}", self).red()),
         }
     }
 }
@@ -94,17 +319,10 @@ impl Add for Expr {
     type Output = EvalResult<Self>;
 
     fn add(self, other: Self) -> Self::Output {
-        let sum = match (self, other) {
-            (Int(a), Int(b)) => Int(a + b),
-            (Int(a), Float(b)) => Float(a as f64 + b),
-            (Float(a), Int(b)) => Float(a + b as f64),
-            (Float(a), Float(b)) => Float(a + b),
-            (Str(a), Str(b)) => Str(format!("{}{}", a, b)),
-            (_, _) => {
-                return Err(EvalError::TypeError);
-            }
-        };
-        Ok(sum)
+        match (self, other) {
+            (Str(a), Str(b)) => Ok(Str(format!("{}{}", a, b))),
+            (a, b) => number::arithmetic("+", a, b, i64::checked_add, |a, b| a + b, |a, b| a + b),
+        }
     }
 }
 
@@ -112,16 +330,14 @@ impl Sub for Expr {
     type Output = EvalResult<Self>;
 
     fn sub(self, other: Self) -> Self::Output {
-        let diff = match (self, other) {
-            (Int(a), Int(b)) => Int(a - b),
-            (Int(a), Float(b)) => Float(a as f64 - b),
-            (Float(a), Int(b)) => Float(a - b as f64),
-            (Float(a), Float(b)) => Float(a - b),
-            (_, _) => {
-                return Err(EvalError::TypeError);
-            }
-        };
-        Ok(diff)
+        number::arithmetic(
+            "-",
+            self,
+            other,
+            i64::checked_sub,
+            |a, b| a - b,
+            |a, b| a - b,
+        )
     }
 }
 
@@ -129,16 +345,14 @@ impl Mul for Expr {
     type Output = EvalResult<Self>;
 
     fn mul(self, other: Self) -> Self::Output {
-        let product = match (self, other) {
-            (Int(a), Int(b)) => Int(a * b),
-            (Int(a), Float(b)) => Float(a as f64 * b),
-            (Float(a), Int(b)) => Float(a * b as f64),
-            (Float(a), Float(b)) => Float(a * b),
-            (_, _) => {
-                return Err(EvalError::TypeError);
-            }
-        };
-        Ok(product)
+        number::arithmetic(
+            "*",
+            self,
+            other,
+            i64::checked_mul,
+            |a, b| a * b,
+            |a, b| a * b,
+        )
     }
 }
 
@@ -146,15 +360,14 @@ impl Div for Expr {
     type Output = EvalResult<Self>;
 
     fn div(self, other: Self) -> Self::Output {
-        let quotient = match (self, other) {
-            (Int(a), Int(b)) => Int(a / b),
-            (Int(a), Float(b)) => Float(a as f64 / b),
-            (Float(a), Int(b)) => Float(a / b as f64),
-            (Float(a), Float(b)) => Float(a / b),
-            (_, _) => {
-                return Err(EvalError::TypeError);
-            }
+        if number::is_exact(&self) && other == Int(0) {
+            return Err(EvalError::DivisionByZero);
+        }
+
+        let checked = |a: i64, b: i64| match a.checked_rem(b) {
+            Some(0) => a.checked_div(b),
+            _ => None,
         };
-        Ok(quotient)
+        number::arithmetic("/", self, other, checked, |a, b| a / b, |a, b| a / b)
     }
 }
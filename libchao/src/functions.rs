@@ -1,13 +1,16 @@
 use crate::{
     Env, Expr, Interpreter,
     interpreter::{EvalError, EvalResult},
+    span::Source,
 };
 
 pub type BuiltinFn = fn(&mut Interpreter, &[Expr]) -> EvalResult<Expr>;
+pub type SpecialFormFn = for<'a> fn(&mut Interpreter, &'a [Expr]) -> EvalResult<Tail<'a>>;
 
 #[derive(Debug, Clone)]
 pub enum Callable {
     Builtin(Builtin),
+    SpecialForm(SpecialForm),
     Lambda(Lambda),
     Macro(Macro),
 }
@@ -16,6 +19,7 @@ impl PartialEq for Callable {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Callable::Builtin(a), Callable::Builtin(b)) => a == b,
+            (Callable::SpecialForm(a), Callable::SpecialForm(b)) => a == b,
             (Callable::Lambda(a), Callable::Lambda(b)) => a == b,
             _ => false,
         }
@@ -37,6 +41,28 @@ impl PartialEq for Builtin {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct SpecialForm {
+    pub arity: Arity,
+    pub f: SpecialFormFn,
+}
+
+impl PartialEq for SpecialForm {
+    fn eq(&self, other: &Self) -> bool {
+        self.arity == other.arity && std::ptr::fn_addr_eq(self.f, other.f)
+    }
+}
+
+// `Eval` borrows one of the forms a special form was given, and `Body` shares
+// the forms of a lambda body or a macro expansion, so neither is copied. A body
+// carries its spans so that errors raised in it can be located.
+#[derive(Debug, Clone)]
+pub enum Tail<'a> {
+    Value(Expr),
+    Eval(&'a Expr, Env),
+    Body(Source, Env),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Arity {
     Exact(usize),
@@ -64,7 +90,7 @@ pub enum EvalMode {
 #[derive(Debug, Clone)]
 pub struct Lambda {
     pub params: LambdaParams,
-    pub body: Box<Expr>,
+    pub body: Source,
     pub env: Env,
 }
 
@@ -77,15 +103,95 @@ impl PartialEq for Lambda {
 #[derive(Debug, Clone, PartialEq)]
 pub enum LambdaParams {
     Fixed(Vec<String>),
+    Variadic {
+        required: Vec<String>,
+        optional: Vec<(String, Expr)>,
+        rest: Option<String>,
+        keys: Vec<(String, Expr)>,
+    },
 }
 
 impl LambdaParams {
-    pub fn bind(&self, args: &[Expr]) -> EvalResult<Vec<(String, Expr)>> {
+    pub fn bind(&self, interpreter: &mut Interpreter, env: &Env, args: &[Expr]) -> EvalResult<Env> {
         match self {
             LambdaParams::Fixed(names) if names.len() == args.len() => {
-                Ok(names.iter().cloned().zip(args.iter().cloned()).collect())
+                Ok(env.child_with(names.iter().cloned().zip(args.iter().cloned()).collect()))
             }
             LambdaParams::Fixed(_) => Err(EvalError::ArityMismatch),
+            LambdaParams::Variadic {
+                required,
+                optional,
+                rest,
+                keys,
+            } => {
+                if args.len() < required.len()
+                    || (rest.is_none()
+                        && keys.is_empty()
+                        && args.len() > required.len() + optional.len())
+                {
+                    return Err(EvalError::ArityMismatch);
+                }
+
+                let mut env = env.child();
+                let (positional, mut remaining) = args.split_at(required.len());
+                for (name, arg) in required.iter().zip(positional) {
+                    env.insert(name.clone(), arg.clone());
+                }
+
+                for (name, default) in optional {
+                    let value = match remaining {
+                        [arg, tail @ ..] => {
+                            remaining = tail;
+                            arg.clone()
+                        }
+                        [] => interpreter
+                            .with_env(env.clone(), |interpreter| interpreter.eval(default))?,
+                    };
+                    env.insert(name.clone(), value);
+                }
+
+                if let Some(name) = rest {
+                    let value = if remaining.is_empty() {
+                        Expr::Nil
+                    } else {
+                        Expr::List(remaining.to_vec())
+                    };
+                    env.insert(name.clone(), value);
+                }
+
+                if keys.is_empty() {
+                    return Ok(env);
+                }
+
+                if remaining.len() % 2 != 0 {
+                    return Err(EvalError::ArityMismatch);
+                }
+
+                let mut given = vec![];
+                for pair in remaining.chunks(2) {
+                    match &pair[0] {
+                        Expr::Symbol(key) | Expr::Keyword(key)
+                            if keys.iter().any(|(name, _)| name == key) =>
+                        {
+                            given.push((key, pair[1].clone()))
+                        }
+                        other => {
+                            return Err(EvalError::UnknownKeywordArgument(format!("{:?}", other)));
+                        }
+                    }
+                }
+
+                for (name, default) in keys {
+                    let value = match given.iter().find(|(key, _)| *key == name) {
+                        Some((_, value)) => value.clone(),
+                        None => interpreter
+                            .with_env(env.clone(), |interpreter| interpreter.eval(default))?,
+                    };
+                    env.insert(name.clone(), value);
+                }
+
+                Ok(env)
+            }
         }
     }
 }
@@ -93,6 +199,6 @@ impl LambdaParams {
 #[derive(Debug, Clone)]
 pub struct Macro {
     pub params: LambdaParams,
-    pub body: Box<Expr>,
+    pub body: Source,
     pub env: Env,
 }
@@ -1,9 +1,16 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
 use crate::{
     Env, Expr, builtin,
-    functions::{Callable, EvalMode},
+    expr::Key,
+    functions::{Callable, EvalMode, Macro, Tail},
+    parser::{self, SyntaxError},
+    span::{Source, Span},
 };
 
-#[derive(Debug, Clone, thiserror::Error)]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
 pub enum EvalError {
     #[error("missing varargs")]
     MissingVarArgs,
@@ -17,18 +24,160 @@ pub enum EvalError {
     CanOnlyInterStrings,
     #[error("can only apply functions")]
     CanOnlyApplyFunctions,
-    #[error("type error")]
-    TypeError,
+    #[error("type error: {op} cannot be applied to {}", describe(.values))]
+    TypeError { op: String, values: Vec<Expr> },
     #[error("def name must be a symbol")]
     DefNameMustBeSymbol,
     #[error("missing argument")]
     ArgumentError,
     #[error("def param must be a symbol")]
     DefParamMustBeSymbol,
-    #[error("unbound variable")]
-    UnboundVariable,
+    #[error("unbound variable {name}{}", did_you_mean(.suggestion))]
+    UnboundVariable {
+        name: String,
+        suggestion: Option<String>,
+    },
     #[error("unquote outside quasiquote")]
     UnquoteOutsideQuasiquote,
+    #[error("unquote-splicing outside list")]
+    SplicingOutsideList,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("{0:?} cannot be used as a map key")]
+    InvalidMapKey(Box<Expr>),
+    #[error("index {index} out of bounds for length {len}")]
+    IndexOutOfBounds { index: i64, len: usize },
+    #[error("invalid {encoding} data {data:?}")]
+    InvalidEncoding {
+        encoding: &'static str,
+        data: Box<Expr>,
+    },
+    #[error("{value:?} does not fit in {width} bytes")]
+    IntegerOverflow { value: Box<Expr>, width: usize },
+    #[error("{op} cannot handle {value}, the limit is {limit}")]
+    LimitExceeded {
+        op: &'static str,
+        value: i64,
+        limit: i64,
+    },
+    #[error("unknown keyword argument {0}")]
+    UnknownKeywordArgument(String),
+    #[error("syntax error: {}", .0.message)]
+    ParseError(SyntaxError),
+    #[error("{message}")]
+    User {
+        kind: String,
+        message: String,
+        payload: Box<Expr>,
+    },
+    #[error("{error}")]
+    Traced { error: Box<EvalError>, span: Span },
+}
+
+fn describe(values: &[Expr]) -> String {
+    values
+        .iter()
+        .map(|value| format!("{:?} ({})", value, value.type_name()))
+        .join(" and ")
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(name) => format!(", did you mean {}?", name),
+        None => String::new(),
+    }
+}
+
+impl EvalError {
+    pub fn type_error(op: &str, values: &[Expr]) -> Self {
+        EvalError::TypeError {
+            op: op.to_string(),
+            values: values.to_vec(),
+        }
+    }
+
+    pub fn unbound(name: &str, env: &Env) -> Self {
+        EvalError::UnboundVariable {
+            name: name.to_string(),
+            suggestion: env.suggest(name),
+        }
+    }
+
+    pub fn kind_name(&self) -> &str {
+        match self {
+            EvalError::MissingVarArgs => "missing-var-args",
+            EvalError::ArityMismatch => "arity-mismatch",
+            EvalError::LambdaNameMustBeSymbol => "lambda-name-must-be-symbol",
+            EvalError::VariableNameMustBeSymbol => "variable-name-must-be-symbol",
+            EvalError::CanOnlyInterStrings => "can-only-intern-strings",
+            EvalError::CanOnlyApplyFunctions => "can-only-apply-functions",
+            EvalError::TypeError { .. } => "type-error",
+            EvalError::DefNameMustBeSymbol => "def-name-must-be-symbol",
+            EvalError::ArgumentError => "argument-error",
+            EvalError::DefParamMustBeSymbol => "def-param-must-be-symbol",
+            EvalError::UnboundVariable { .. } => "unbound-variable",
+            EvalError::UnquoteOutsideQuasiquote => "unquote-outside-quasiquote",
+            EvalError::SplicingOutsideList => "splicing-outside-list",
+            EvalError::DivisionByZero => "division-by-zero",
+            EvalError::InvalidMapKey(_) => "invalid-map-key",
+            EvalError::IndexOutOfBounds { .. } => "index-out-of-bounds",
+            EvalError::InvalidEncoding { .. } => "invalid-encoding",
+            EvalError::IntegerOverflow { .. } => "integer-overflow",
+            EvalError::LimitExceeded { .. } => "limit-exceeded",
+            EvalError::UnknownKeywordArgument(_) => "unknown-keyword-argument",
+            EvalError::ParseError(_) => "parse-error",
+            EvalError::User { kind, .. } => kind,
+            EvalError::Traced { error, .. } => error.kind_name(),
+        }
+    }
+
+    pub fn payload(&self) -> Expr {
+        match self.kind() {
+            EvalError::TypeError { values, .. } => Expr::List(values.clone()),
+            EvalError::UnboundVariable { name, .. } => Expr::Symbol(name.clone()),
+            EvalError::InvalidMapKey(key) => key.as_ref().clone(),
+            EvalError::IndexOutOfBounds { index, .. } => Expr::Int(*index),
+            EvalError::InvalidEncoding { data, .. } => data.as_ref().clone(),
+            EvalError::IntegerOverflow { value, .. } => value.as_ref().clone(),
+            EvalError::LimitExceeded { value, .. } => Expr::Int(*value),
+            EvalError::UnknownKeywordArgument(key) => Expr::Str(key.clone()),
+            EvalError::User { payload, .. } => *payload.clone(),
+            _ => Expr::Nil,
+        }
+    }
+
+    pub fn kind(&self) -> &EvalError {
+        match self {
+            EvalError::Traced { error, .. } => error.kind(),
+            other => other,
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            EvalError::Traced { span, .. } => Some(*span),
+            EvalError::ParseError(err) => Some(err.span()),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, source: &str, name: &str) -> String {
+        match self.span() {
+            Some(span) => span.render(source, name, &self.to_string()),
+            None => format!("{}: error: {}", name, self),
+        }
+    }
+
+    // Blames an error that could not be located more precisely on `span`.
+    fn or_at(self, span: Span) -> Self {
+        match self {
+            EvalError::Traced { .. } => self,
+            error => EvalError::Traced {
+                error: Box::new(error),
+                span,
+            },
+        }
+    }
 }
 
 pub type EvalResult<T> = Result<T, EvalError>;
@@ -36,6 +185,9 @@ pub type EvalResult<T> = Result<T, EvalError>;
 #[derive(Debug, Clone)]
 pub struct Interpreter {
     pub(crate) env: Env,
+    // The forms being evaluated, in which failing forms are looked up.
+    source: Option<Source>,
+    gensym_counter: usize,
 }
 
 impl Default for Interpreter {
@@ -48,7 +200,15 @@ impl Interpreter {
     pub fn new() -> Self {
         let mut env = Env::new();
         builtin::load(&mut env);
-        Self { env }
+        Self {
+            env,
+            source: None,
+            gensym_counter: 0,
+        }
+    }
+
+    pub fn define(&mut self, name: &str, value: Expr) {
+        self.env.insert(name.to_string(), value);
     }
 
     pub(crate) fn with_env<T>(
@@ -63,76 +223,492 @@ impl Interpreter {
     }
 
     pub fn eval(&mut self, expr: &Expr) -> EvalResult<Expr> {
+        let env = self.env.clone();
+        let result = self.eval_tail(Tail::Eval(expr, env.clone()));
+        self.env = env;
+        result
+    }
+
+    // Follows tail calls in a loop so that they run in constant stack space.
+    // A body with spans becomes the source that errors are located in until
+    // the loop ends. Errors raised in a body without spans, such as a macro
+    // expansion, are blamed on the form that called it.
+    fn eval_tail(&mut self, mut tail: Tail) -> EvalResult<Expr> {
+        let mut outer = None;
+        let mut caller = None;
+        let mut form = std::ptr::null();
+        let result = loop {
+            let next = match tail {
+                Tail::Value(value) => break Ok(value),
+                Tail::Eval(expr, env) => {
+                    self.env = env;
+                    self.eval_in_place(expr, &mut form)
+                }
+                Tail::Body(body, env) => {
+                    self.env = env;
+                    if body.has_spans() {
+                        outer.get_or_insert(self.source.replace(body.clone()));
+                        caller = None;
+                    } else {
+                        caller.get_or_insert(form);
+                    }
+                    self.eval_body_in_place(body.exprs(), &mut form)
+                }
+            };
+            match next {
+                Ok(next) => tail = next,
+                Err(err) => match caller {
+                    Some(caller) => break Err(self.locate(err, caller)),
+                    None => break Err(err),
+                },
+            }
+        };
+
+        if let Some(source) = outer {
+            self.source = source;
+        }
+        result
+    }
+
+    fn eval_body_in_place<'a>(
+        &mut self,
+        body: &[Expr],
+        form: &mut *const Expr,
+    ) -> EvalResult<Tail<'a>> {
+        let [init @ .., last] = body else {
+            return Ok(Tail::Value(Expr::Nil));
+        };
+        for expr in init {
+            self.eval(expr)?;
+        }
+        self.eval_in_place(last, form)
+    }
+
+    // Evaluates `expr` and the tail forms it borrows from itself, returning
+    // once there is a value or a body that owns its forms. `form` is set to
+    // the form that returned the body.
+    fn eval_in_place<'a>(
+        &mut self,
+        mut expr: &Expr,
+        form: &mut *const Expr,
+    ) -> EvalResult<Tail<'a>> {
+        loop {
+            let Expr::List(list) = expr else {
+                return self
+                    .eval_value(expr)
+                    .map(Tail::Value)
+                    .map_err(|err| self.locate(err, expr));
+            };
+
+            match self.eval_list(list) {
+                Ok(Tail::Value(value)) => return Ok(Tail::Value(value)),
+                Ok(Tail::Body(body, env)) => {
+                    *form = expr;
+                    return Ok(Tail::Body(body, env));
+                }
+                Ok(Tail::Eval(next, env)) => {
+                    self.env = env;
+                    expr = next;
+                }
+                Err(err) => return Err(self.locate(err, expr)),
+            }
+        }
+    }
+
+    // Records where an error was raised, unless a form inside `expr` already
+    // has. Forms that are not part of the current source stay unlocated.
+    fn locate(&self, err: EvalError, expr: *const Expr) -> EvalError {
+        match err {
+            EvalError::Traced { .. } | EvalError::ParseError(_) => err,
+            error => match self.source.as_ref().and_then(|source| source.span_of(expr)) {
+                Some(span) => EvalError::Traced {
+                    error: Box::new(error),
+                    span,
+                },
+                None => error,
+            },
+        }
+    }
+
+    pub(crate) fn source_of(&self, body: &[Expr]) -> Source {
+        match self
+            .source
+            .as_ref()
+            .and_then(|source| source.spans_of(body))
+        {
+            Some(spans) => Source::with_spans(body.to_vec(), spans),
+            None => Source::new(body.to_vec()),
+        }
+    }
+
+    fn eval_value(&mut self, expr: &Expr) -> EvalResult<Expr> {
         match expr {
             Expr::Nil
             | Expr::Bool(_)
             | Expr::Int(_)
+            | Expr::BigInt(_)
+            | Expr::Ratio(_)
             | Expr::Float(_)
             | Expr::Str(_)
-            | Expr::Callable(_) => Ok(expr.clone()),
-            Expr::Symbol(symbol) => Ok(self.env.get(symbol).unwrap_or(Expr::Nil)),
-            Expr::Quote(expr) => Ok(*expr.clone()),
-            Expr::QuasiQuote(expr) => self.eval_quasiquote(expr, 1),
-            Expr::Unquote(_expr) => Err(EvalError::UnquoteOutsideQuasiquote),
-            Expr::List(list) => self.eval_list(list),
+            | Expr::Char(_)
+            | Expr::Bytes(_)
+            | Expr::Keyword(_)
+            | Expr::Callable(_)
+            | Expr::Error(_) => Ok(expr.clone()),
+            Expr::List(_) => unreachable!("lists are evaluated by eval_in_place"),
+            Expr::Symbol(symbol) => self
+                .env
+                .get(symbol)
+                .ok_or_else(|| EvalError::unbound(symbol, &self.env)),
+            Expr::Quote(expr) => Ok(expr.as_ref().clone()),
+            Expr::QuasiQuote(expr) => self.eval_quasiquote(expr, 1, &mut HashMap::new()),
+            Expr::Unquote(_expr) | Expr::UnquoteSplicing(_expr) => {
+                Err(EvalError::UnquoteOutsideQuasiquote)
+            }
+            Expr::Vector(items) => items
+                .iter()
+                .map(|item| self.eval(item))
+                .collect::<EvalResult<_>>()
+                .map(Expr::Vector),
+            Expr::Map(map) => map
+                .iter()
+                .map(|(key, value)| Ok((Key::new(self.eval(key.expr())?)?, self.eval(value)?)))
+                .collect::<EvalResult<_>>()
+                .map(Expr::Map),
+        }
+    }
+
+    pub fn eval_program(&mut self, exprs: &[Expr]) -> EvalResult<Expr> {
+        let mut result = Expr::Nil;
+        for expr in exprs {
+            result = self.eval(expr)?;
+        }
+        Ok(result)
+    }
+
+    pub fn eval_str(&mut self, input: &str) -> EvalResult<Expr> {
+        let forms = parser::parse_forms(input).map_err(EvalError::ParseError)?;
+
+        let mut result = Expr::Nil;
+        for form in forms {
+            let span = form.span();
+            let source = Source::with_spans(vec![form.expr], vec![form.spans]);
+            let previous = self.source.replace(source.clone());
+            let evaluated = self.eval(&source.exprs()[0]);
+            self.source = previous;
+            result = evaluated.map_err(|err| err.or_at(span))?;
         }
+        Ok(result)
     }
 
-    fn eval_quasiquote(&mut self, expr: &Expr, depth: usize) -> EvalResult<Expr> {
+    pub fn gensym(&mut self, prefix: &str) -> Expr {
+        self.gensym_counter += 1;
+        Expr::Symbol(format!("{}#{}", prefix, self.gensym_counter))
+    }
+
+    fn expand_macro(&mut self, macro_: &Macro, args: &[Expr]) -> EvalResult<Expr> {
+        let env = macro_.params.bind(self, &macro_.env, args)?;
+        self.with_env(env.clone(), |interpreter| {
+            interpreter.eval_tail(Tail::Body(macro_.body.clone(), env))
+        })
+    }
+
+    pub fn macroexpand_1(&mut self, expr: &Expr) -> EvalResult<Option<Expr>> {
+        let Expr::List(list) = expr else {
+            return Ok(None);
+        };
+        let [Expr::Symbol(head), args @ ..] = list.as_slice() else {
+            return Ok(None);
+        };
+
+        match self.env.get(head) {
+            Some(Expr::Callable(Callable::Macro(macro_))) => {
+                self.expand_macro(&macro_, args).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn macroexpand(&mut self, expr: &Expr) -> EvalResult<Expr> {
+        let mut expr = expr.clone();
+        while let Some(expansion) = self.macroexpand_1(&expr)? {
+            expr = expansion;
+        }
+        Ok(expr)
+    }
+
+    pub fn expand_all(&mut self, expr: &Expr) -> EvalResult<Expr> {
+        match self.macroexpand(expr)? {
+            Expr::List(items) => self.expand_form(&items).map(Expr::List),
+            Expr::Vector(items) => items
+                .iter()
+                .map(|item| self.expand_all(item))
+                .collect::<EvalResult<_>>()
+                .map(Expr::Vector),
+            Expr::Map(map) => map
+                .iter()
+                .map(|(key, value)| {
+                    Ok((
+                        Key::new(self.expand_all(key.expr())?)?,
+                        self.expand_all(value)?,
+                    ))
+                })
+                .collect::<EvalResult<_>>()
+                .map(Expr::Map),
+            other => Ok(other),
+        }
+    }
+
+    // Parameter lists, binding names, `case` datums and `catch` headers are
+    // not expressions, and neither are the clauses of `let`, `cond`, `case`
+    // and `try` themselves, so only the expressions inside them are expanded.
+    fn expand_form(&mut self, items: &[Expr]) -> EvalResult<Vec<Expr>> {
+        let head = match items.first() {
+            Some(Expr::Symbol(head)) => head.as_str(),
+            _ => "",
+        };
+
+        let (kept, rest) = match (head, items) {
+            ("lambda", [_, _, ..]) => items.split_at(2),
+            ("def", [_, _, Expr::List(_) | Expr::Nil, _, ..]) | ("defmacro", [_, _, _, ..]) => {
+                items.split_at(3)
+            }
+            ("let" | "let*" | "letrec", [_, Expr::List(bindings), body @ ..]) => {
+                let bindings = bindings
+                    .iter()
+                    .map(|binding| self.expand_clause(binding, 1))
+                    .collect::<EvalResult<_>>()?;
+                let mut result = vec![items[0].clone(), Expr::List(bindings)];
+                result.extend(self.expand_each(body)?);
+                return Ok(result);
+            }
+            ("cond", [_, clauses @ ..]) => {
+                let mut result = vec![items[0].clone()];
+                for clause in clauses {
+                    result.push(self.expand_clause(clause, 0)?);
+                }
+                return Ok(result);
+            }
+            ("case", [_, key, clauses @ ..]) => {
+                let mut result = vec![items[0].clone(), self.expand_all(key)?];
+                for clause in clauses {
+                    result.push(self.expand_clause(clause, 1)?);
+                }
+                return Ok(result);
+            }
+            ("try", [_, forms @ ..]) => {
+                let mut result = vec![items[0].clone()];
+                for form in forms {
+                    let header = match form {
+                        Expr::List(clause) => match clause.as_slice() {
+                            [Expr::Symbol(head), Expr::Symbol(_), ..] if head == "catch" => 2,
+                            [Expr::Symbol(head), _, Expr::Symbol(_), ..] if head == "catch" => 3,
+                            [Expr::Symbol(head), ..] if head == "finally" => 1,
+                            _ => 0,
+                        },
+                        _ => 0,
+                    };
+                    result.push(match header {
+                        0 => self.expand_all(form)?,
+                        header => self.expand_clause(form, header)?,
+                    });
+                }
+                return Ok(result);
+            }
+            _ => items.split_at(0),
+        };
+
+        let mut result = kept.to_vec();
+        result.extend(self.expand_each(rest)?);
+        Ok(result)
+    }
+
+    // Keeps the first `kept` items of a clause and expands the rest.
+    fn expand_clause(&mut self, clause: &Expr, kept: usize) -> EvalResult<Expr> {
+        match clause {
+            Expr::List(items) if items.len() >= kept => {
+                let mut result = items[..kept].to_vec();
+                result.extend(self.expand_each(&items[kept..])?);
+                Ok(Expr::List(result))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn expand_each(&mut self, items: &[Expr]) -> EvalResult<Vec<Expr>> {
+        items.iter().map(|item| self.expand_all(item)).collect()
+    }
+
+    fn eval_quasiquote(
+        &mut self,
+        expr: &Expr,
+        depth: usize,
+        gensyms: &mut HashMap<String, Expr>,
+    ) -> EvalResult<Expr> {
         match expr {
+            Expr::Symbol(name) if name.len() > 1 && name.ends_with('#') => Ok(gensyms
+                .entry(name.clone())
+                .or_insert_with(|| self.gensym(&name[..name.len() - 1]))
+                .clone()),
             Expr::Unquote(inner) if depth == 1 => self.eval(inner),
-            Expr::Unquote(inner) => Ok(Expr::Unquote(Box::new(
-                self.eval_quasiquote(inner, depth - 1)?,
+            Expr::Unquote(inner) => Ok(Expr::Unquote(Box::new(self.eval_quasiquote(
+                inner,
+                depth - 1,
+                gensyms,
+            )?))),
+            Expr::UnquoteSplicing(_) if depth == 1 => Err(EvalError::SplicingOutsideList),
+            Expr::UnquoteSplicing(inner) => Ok(Expr::UnquoteSplicing(Box::new(
+                self.eval_quasiquote(inner, depth - 1, gensyms)?,
             ))),
-            Expr::QuasiQuote(inner) => Ok(Expr::QuasiQuote(Box::new(
-                self.eval_quasiquote(inner, depth + 1)?,
+            Expr::QuasiQuote(inner) => Ok(Expr::QuasiQuote(Box::new(self.eval_quasiquote(
+                inner,
+                depth + 1,
+                gensyms,
+            )?))),
+            Expr::Quote(inner) => Ok(Expr::Quote(Box::new(
+                self.eval_quasiquote(inner, depth, gensyms)?,
             ))),
-            Expr::List(items) => items
+            Expr::List(items) => {
+                let result = self.quasiquote_items(items.iter(), depth, gensyms)?;
+                if result.is_empty() {
+                    Ok(Expr::Nil)
+                } else {
+                    Ok(Expr::List(result))
+                }
+            }
+            Expr::Vector(items) => self
+                .quasiquote_items(items.iter(), depth, gensyms)
+                .map(|result| Expr::Vector(result.into())),
+            Expr::Map(map) => map
                 .iter()
-                .map(|item| self.eval_quasiquote(item, depth))
-                .collect::<EvalResult<Vec<_>>>()
-                .map(Expr::List),
+                .map(|(key, value)| {
+                    let key = self.eval_quasiquote(key.expr(), depth, gensyms)?;
+                    Ok((Key::new(key)?, self.eval_quasiquote(value, depth, gensyms)?))
+                })
+                .collect::<EvalResult<_>>()
+                .map(Expr::Map),
             other => Ok(other.clone()),
         }
     }
 
-    fn eval_list(&mut self, list: &[Expr]) -> EvalResult<Expr> {
+    fn quasiquote_items<'a>(
+        &mut self,
+        items: impl Iterator<Item = &'a Expr>,
+        depth: usize,
+        gensyms: &mut HashMap<String, Expr>,
+    ) -> EvalResult<Vec<Expr>> {
+        let mut result = vec![];
+        for item in items {
+            match item {
+                Expr::UnquoteSplicing(inner) if depth == 1 => match self.eval(inner)? {
+                    Expr::List(values) => result.extend(values),
+                    Expr::Vector(values) => result.extend(values),
+                    Expr::Nil => {}
+                    value => return Err(EvalError::type_error(",@", &[value])),
+                },
+                item => result.push(self.eval_quasiquote(item, depth, gensyms)?),
+            }
+        }
+        Ok(result)
+    }
+
+    fn eval_list<'a>(&mut self, list: &'a [Expr]) -> EvalResult<Tail<'a>> {
         let [head, tail @ ..] = list else {
-            return Ok(Expr::Nil);
+            return Ok(Tail::Value(Expr::Nil));
         };
 
-        let callable = self.eval(head)?;
+        let callable = match self.eval(head)? {
+            Expr::Callable(callable) => callable,
+            keyword @ Expr::Keyword(_) => return self.call_keyword(&keyword, tail),
+            _ => return Err(EvalError::CanOnlyApplyFunctions),
+        };
 
-        match callable {
-            Expr::Callable(Callable::Builtin(builtin)) => {
-                let args = match builtin.mode {
-                    EvalMode::Eager => self.eval_args(tail)?,
-                    EvalMode::Raw => tail.to_vec(),
-                };
+        let args = match &callable {
+            Callable::SpecialForm(special_form) => {
+                special_form.arity.check(tail)?;
+                return (special_form.f)(self, tail);
+            }
+            Callable::Macro(macro_) => return self.call_macro(macro_, tail),
+            Callable::Builtin(builtin) if builtin.mode == EvalMode::Raw => {
+                builtin.arity.check(tail)?;
+                return (builtin.f)(self, tail).map(Tail::Value);
+            }
+            _ => self.eval_args(tail)?,
+        };
+        self.call(&callable, args)
+    }
 
-                builtin.arity.check(&args)?;
-                (builtin.f)(self, &args)
+    fn call_keyword(&mut self, keyword: &Expr, args: &[Expr]) -> EvalResult<Tail<'static>> {
+        let args = self.eval_args(args)?;
+        apply_keyword(keyword, &args).map(Tail::Value)
+    }
+
+    fn call_macro(&mut self, macro_: &Macro, args: &[Expr]) -> EvalResult<Tail<'static>> {
+        let expansion = self.expand_macro(macro_, args)?;
+        Ok(Tail::Body(Source::new(vec![expansion]), self.env.clone()))
+    }
+
+    pub fn apply(&mut self, callable: &Callable, args: Vec<Expr>) -> EvalResult<Expr> {
+        let tail = match callable {
+            Callable::Builtin(builtin) if builtin.mode == EvalMode::Eager => {
+                self.call(callable, args)?
             }
-            Expr::Callable(Callable::Lambda(lambda)) => {
-                let args = self.eval_args(tail)?;
-                let bindings = lambda.params.bind(&args)?;
+            Callable::Lambda(_) => self.call(callable, args)?,
+            _ => return Err(EvalError::CanOnlyApplyFunctions),
+        };
 
-                self.with_env(lambda.env.child_with(bindings), |interpreter| {
-                    interpreter.eval(&lambda.body)
-                })
+        let env = self.env.clone();
+        let result = self.eval_tail(tail);
+        self.env = env;
+        result
+    }
+
+    // Calls a builtin or lambda with evaluated arguments. A lambda body is
+    // handed back to be evaluated in tail position.
+    fn call(&mut self, callable: &Callable, args: Vec<Expr>) -> EvalResult<Tail<'static>> {
+        match callable {
+            Callable::Builtin(builtin) => {
+                builtin.arity.check(&args)?;
+                (builtin.f)(self, &args).map(Tail::Value)
             }
-            Expr::Callable(Callable::Macro(macro_)) => {
-                let bindings = macro_.params.bind(tail)?;
-                let expansion = self.with_env(macro_.env.child_with(bindings), |interpreter| {
-                    interpreter.eval(&macro_.body)
-                })?;
-                self.eval(&expansion)
+            Callable::Lambda(lambda) => {
+                let env = lambda.params.bind(self, &lambda.env, &args)?;
+                Ok(Tail::Body(lambda.body.clone(), env))
             }
             _ => Err(EvalError::CanOnlyApplyFunctions),
         }
     }
 
+    pub(crate) fn eval_body<'a>(&mut self, body: &'a [Expr], env: Env) -> EvalResult<Tail<'a>> {
+        let [init @ .., last] = body else {
+            return Ok(Tail::Value(Expr::Nil));
+        };
+
+        self.with_env(env.clone(), |interpreter| {
+            init.iter()
+                .try_for_each(|expr| interpreter.eval(expr).map(drop))
+        })?;
+        Ok(Tail::Eval(last, env))
+    }
+
     fn eval_args(&mut self, args: &[Expr]) -> EvalResult<Vec<Expr>> {
-        args.iter().map(|arg| self.eval(arg)).collect()
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            values.push(self.eval(arg)?);
+        }
+        Ok(values)
     }
 }
+
+// A keyword looks itself up in the collection it is applied to, falling back
+// to an optional default.
+pub(crate) fn apply_keyword(keyword: &Expr, args: &[Expr]) -> EvalResult<Expr> {
+    let (coll, default) = match args {
+        [coll] => (coll, Expr::Nil),
+        [coll, default] => (coll, default.clone()),
+        _ => return Err(EvalError::ArityMismatch),
+    };
+    let op = format!("{:?}", keyword);
+    let value = builtin::lookup(&op, coll, keyword)?;
+    Ok(value.unwrap_or(default))
+}
@@ -3,9 +3,12 @@ pub mod env;
 pub mod expr;
 pub mod functions;
 pub mod interpreter;
+mod number;
 pub mod parser;
+pub mod span;
 
 pub use env::Env;
 pub use expr::Expr;
 pub use interpreter::Interpreter;
-pub use parser::parse;
+pub use parser::{SyntaxError, parse, parse_forms, parse_program};
+pub use span::{Form, Position, Source, Span};
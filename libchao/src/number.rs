@@ -0,0 +1,93 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::expr::Expr;
+use crate::interpreter::{EvalError, EvalResult};
+
+// Exact results are always normalized, so an integer that fits in an `i64`
+// is never a `BigInt` and a ratio with denominator 1 is never a `Ratio`.
+// This keeps structural equality on `Expr` meaningful for numbers.
+pub fn normalize(n: BigRational) -> Expr {
+    if n.is_integer() {
+        from_bigint(n.to_integer())
+    } else {
+        Expr::Ratio(n)
+    }
+}
+
+pub fn from_bigint(n: BigInt) -> Expr {
+    match n.to_i64() {
+        Some(n) => Expr::Int(n),
+        None => Expr::BigInt(n),
+    }
+}
+
+pub fn is_exact(expr: &Expr) -> bool {
+    matches!(expr, Expr::Int(_) | Expr::BigInt(_) | Expr::Ratio(_))
+}
+
+pub fn to_bigint(expr: &Expr) -> Option<BigInt> {
+    match expr {
+        Expr::Int(n) => Some(BigInt::from(*n)),
+        Expr::BigInt(n) => Some(n.clone()),
+        _ => None,
+    }
+}
+
+pub fn to_rational(expr: &Expr) -> Option<BigRational> {
+    match expr {
+        Expr::Int(n) => Some(BigRational::from_integer(BigInt::from(*n))),
+        Expr::BigInt(n) => Some(BigRational::from_integer(n.clone())),
+        Expr::Ratio(n) => Some(n.clone()),
+        _ => None,
+    }
+}
+
+pub fn to_f64(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Int(n) => Some(*n as f64),
+        Expr::BigInt(n) => n.to_f64(),
+        Expr::Ratio(n) => n.to_f64(),
+        Expr::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+pub fn from_f64(f: f64) -> Option<Expr> {
+    BigRational::from_f64(f).map(normalize)
+}
+
+pub fn arithmetic(
+    op: &str,
+    a: Expr,
+    b: Expr,
+    checked: fn(i64, i64) -> Option<i64>,
+    exact: fn(BigRational, BigRational) -> BigRational,
+    inexact: fn(f64, f64) -> f64,
+) -> EvalResult<Expr> {
+    if let (Expr::Int(x), Expr::Int(y)) = (&a, &b)
+        && let Some(n) = checked(*x, *y)
+    {
+        return Ok(Expr::Int(n));
+    }
+
+    if let (Some(x), Some(y)) = (to_rational(&a), to_rational(&b)) {
+        return Ok(normalize(exact(x, y)));
+    }
+
+    match (to_f64(&a), to_f64(&b)) {
+        (Some(x), Some(y)) => Ok(Expr::Float(inexact(x, y))),
+        _ => Err(EvalError::type_error(op, &[a, b])),
+    }
+}
+
+pub fn compare(a: &Expr, b: &Expr) -> Option<Ordering> {
+    match (a, b) {
+        (Expr::Int(a), Expr::Int(b)) => a.partial_cmp(b),
+        (Expr::Float(_), _) | (_, Expr::Float(_)) => to_f64(a)?.partial_cmp(&to_f64(b)?),
+        _ => to_rational(a)?.partial_cmp(&to_rational(b)?),
+    }
+}
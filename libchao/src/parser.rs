@@ -1,60 +1,201 @@
-use combine::error::{Commit, ParseError, StdParseResult};
-use combine::parser::char::{char as c, digit, hex_digit, letter, spaces, string};
+use std::fmt;
+
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Num;
+
+use combine::error::{Commit, ParseError, StdParseResult, StreamError};
+use combine::parser::char::{char as c, hex_digit, space, string};
+use combine::parser::repeat::count_min_max;
+use combine::stream::{StreamErrorFor, easy, position};
 use combine::{
-    EasyParser, Parser, Stream, any, attempt, between, choice, eof, many, many1, one_of, optional,
-    parser, satisfy_map,
+    EasyParser, Parser, Stream, any, attempt, between, choice, eof, many, none_of, not_followed_by,
+    one_of, optional, parser, satisfy, satisfy_map, skip_many, skip_many1,
 };
 
-use crate::expr::Expr::{self, *};
+use crate::expr::{
+    CHAR_NAMES,
+    Expr::{self, *},
+    Key,
+};
+use crate::number;
+use crate::span::{Form, Position, Span, SpanTree};
 
-fn int<I>() -> impl Parser<I, Output = Expr>
-where
-    I: Stream<Token = char>,
-    I::Error: ParseError<I::Token, I::Range, I::Position>,
-{
-    let binary = (string("0b"), many1(one_of("01".chars())))
-        .map(|(_, s): (_, String)| i64::from_str_radix(&s, 2).unwrap());
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{position}: {message}")]
+pub struct SyntaxError {
+    pub position: Position,
+    pub message: String,
+}
+
+impl SyntaxError {
+    pub fn span(&self) -> Span {
+        Span::point(self.position)
+    }
+}
+
+type Errors<'a> = easy::Errors<char, &'a str, position::SourcePosition>;
+
+impl From<Errors<'_>> for SyntaxError {
+    fn from(err: Errors<'_>) -> Self {
+        struct Message<'a>(&'a [easy::Error<char, &'a str>]);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                easy::Error::fmt_errors(self.0, f)
+            }
+        }
+
+        Self {
+            position: to_position(err.position),
+            message: Message(&err.errors).to_string().lines().join("; "),
+        }
+    }
+}
+
+fn to_position(position: position::SourcePosition) -> Position {
+    Position {
+        line: position.line as usize,
+        column: position.column as usize,
+    }
+}
+
+fn is_digits(digits: &str, radix: u32) -> bool {
+    digits.starts_with(|c: char| c.is_digit(radix))
+        && digits.chars().all(|c| c.is_digit(radix) || c == '_')
+}
+
+fn integer_literal(sign: &str, digits: &str, radix: u32) -> Result<BigInt, &'static str> {
+    let digits = format!("{}{}", sign, digits.replace('_', ""));
+    BigInt::from_str_radix(&digits, radix).map_err(|_| "invalid number literal")
+}
+
+// Returns `Ok(None)` for tokens that don't start like a number, so they can
+// be read as symbols instead.
+fn number(token: &str) -> Result<Option<Expr>, &'static str> {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let sign = &token[..token.len() - unsigned.len()];
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    if let Some((numerator, denominator)) = digits.split_once('/') {
+        if radix != 10 || !is_digits(numerator, 10) || !is_digits(denominator, 10) {
+            return Err("invalid number literal");
+        }
+        let numerator = integer_literal(sign, numerator, 10)?;
+        let denominator = integer_literal("", denominator, 10)?;
+        if denominator == BigInt::ZERO {
+            return Err("ratio literal with zero denominator");
+        }
+        return Ok(Some(number::normalize(BigRational::new(
+            numerator,
+            denominator,
+        ))));
+    }
+
+    let (mantissa, exponent) = match digits.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) if radix == 10 => (mantissa, Some(exponent)),
+        _ => (digits, None),
+    };
+    let (integer, fraction) = match mantissa.split_once('.') {
+        Some((integer, fraction)) if radix == 10 => (integer, Some(fraction)),
+        _ => (mantissa, None),
+    };
 
-    let hex = (string("0x"), many1(hex_digit()))
-        .map(|(_, s): (_, String)| i64::from_str_radix(&s, 16).unwrap());
+    let valid = is_digits(integer, radix)
+        && fraction.is_none_or(|fraction| is_digits(fraction, 10))
+        && exponent.is_none_or(|exponent| {
+            is_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10)
+        });
+    if !valid {
+        return Err("invalid number literal");
+    }
 
-    let decimal = many1(digit()).map(|s: String| s.parse::<i64>().unwrap());
+    if fraction.is_none() && exponent.is_none() {
+        return integer_literal(sign, integer, radix).map(|n| Some(number::from_bigint(n)));
+    }
 
-    choice((attempt(binary), attempt(hex), attempt(decimal)))
-        .skip(spaces())
-        .map(Int)
+    match token.replace('_', "").parse() {
+        Ok(f) => Ok(Some(Float(f))),
+        Err(_) => Err("invalid number literal"),
+    }
 }
 
-fn float<I>() -> impl Parser<I, Output = Expr>
+fn classify(token: String) -> Result<Expr, &'static str> {
+    match token.as_str() {
+        "nil" => return Ok(Nil),
+        "true" => return Ok(Bool(true)),
+        "false" => return Ok(Bool(false)),
+        _ => {}
+    }
+
+    if let Some(name) = token.strip_prefix(':') {
+        return match name {
+            "" => Err("invalid keyword"),
+            name if name.contains('#') => Err("invalid keyword"),
+            name => Ok(Keyword(name.to_string())),
+        };
+    }
+
+    if let Some(number) = number(&token)? {
+        return Ok(number);
+    }
+
+    // `#` may only end a symbol, where it marks an auto-gensym.
+    match token.find('#') {
+        Some(index) if index != token.len() - 1 => Err("invalid symbol"),
+        _ => Ok(Symbol(token)),
+    }
+}
+
+fn atom<I>() -> impl Parser<I, Output = Expr>
 where
     I: Stream<Token = char>,
     I::Error: ParseError<I::Token, I::Range, I::Position>,
 {
-    attempt((many1(digit()), string("."), many1(digit())))
-        .skip(spaces())
-        .map(|(a, b, c): (String, &str, String)| {
-            Float([a, b.to_string(), c].join("").parse::<f64>().unwrap())
-        })
+    let constituent = || satisfy(|c: char| c.is_alphanumeric() || "+-*/^&|%!=<>?_$~".contains(c));
+    let subsequent = constituent().or(one_of(".#".chars()));
+
+    (constituent().or(c(':')), many(subsequent)).and_then(|(first, rest): (char, String)| {
+        classify(format!("{}{}", first, rest)).map_err(StreamErrorFor::<I>::message_static_message)
+    })
 }
 
-fn boolean<I>() -> impl Parser<I, Output = Expr>
+fn simple_escape<I>() -> impl Parser<I, Output = char>
 where
     I: Stream<Token = char>,
     I::Error: ParseError<I::Token, I::Range, I::Position>,
 {
-    let t = || string("true").map(|_| Bool(true));
-    let f = || string("false").map(|_| Bool(false));
-    (attempt(choice((t(), f()))), spaces()).map(|(b, _)| b)
+    satisfy_map(|c| {
+        Some(match c {
+            '"' => '"',
+            '\\' => '\\',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            _ => return None,
+        })
+    })
 }
 
-fn symbol<I>() -> impl Parser<I, Output = Expr>
+fn hex_escape<I>() -> impl Parser<I, Output = u8>
 where
     I: Stream<Token = char>,
     I::Error: ParseError<I::Token, I::Range, I::Position>,
 {
-    many1(letter().or(one_of("+-*/^&|%!=><".chars())))
-        .skip(spaces())
-        .map(Symbol)
+    c('x')
+        .with(count_min_max(2, 2, hex_digit()))
+        .map(|digits: String| u8::from_str_radix(&digits, 16).unwrap())
 }
 
 fn sstring<I>() -> impl Parser<I, Output = Expr>
@@ -64,70 +205,240 @@ where
 {
     let string_char = parser(|input: &mut I| {
         let result: StdParseResult<char, I> = any().parse_stream(input).into_result();
-        let (c, committed) = result?;
-        let mut back_slash_char = satisfy_map(|c| {
-            Some(match c {
-                '"' => '"',
-                '\\' => '\\',
-                'n' => '\n',
-                'r' => '\r',
-                't' => '\t',
-                _ => return None,
-            })
-        });
-        match c {
+        let (token, committed) = result?;
+        let mut back_slash_char = choice((
+            simple_escape(),
+            hex_escape().map(char::from),
+            c('u')
+                .with(between(c('{'), c('}'), count_min_max(1, 6, hex_digit())))
+                .and_then(|digits: String| {
+                    u32::from_str_radix(&digits, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| {
+                            StreamErrorFor::<I>::message_static_message("invalid unicode escape")
+                        })
+                }),
+        ));
+        match token {
             '\\' => committed.combine(|_| back_slash_char.parse_stream(input).into_result()),
             '"' => Err(Commit::Peek(I::Error::empty(input.position()).into())),
-            _ => Ok((c, committed)),
+            _ => Ok((token, committed)),
         }
     });
 
-    between(c('"'), c('"').skip(spaces()), many(string_char)).map(Str)
+    between(c('"'), c('"'), many(string_char)).map(Str)
+}
+
+fn byte_string<I>() -> impl Parser<I, Output = Expr>
+where
+    I: Stream<Token = char>,
+    I::Error: ParseError<I::Token, I::Range, I::Position>,
+{
+    let byte = choice((
+        c('\\').with(choice((simple_escape().map(|c| c as u8), hex_escape()))),
+        none_of("\"\\".chars()).and_then(|c: char| {
+            u8::try_from(c).ok().filter(u8::is_ascii).ok_or_else(|| {
+                StreamErrorFor::<I>::message_static_message("non-ASCII character in byte string")
+            })
+        }),
+    ));
+
+    attempt(string("#b\""))
+        .with(many(byte))
+        .skip(c('"'))
+        .map(Bytes)
 }
 
-fn nil<I>() -> impl Parser<I, Output = Expr>
+// `r"..."` strings have no escapes, and `r#"..."#` strings may contain
+// quotes not followed by as many `#` as the opening delimiter.
+fn raw_string<I>() -> impl Parser<I, Output = Expr>
 where
     I: Stream<Token = char>,
     I::Error: ParseError<I::Token, I::Range, I::Position>,
 {
-    attempt(string("nil")).skip(spaces()).map(|_| Nil)
+    attempt((c('r'), many(c('#')), c('"'))).then(|(_, hashes, _): (char, String, char)| {
+        let n = hashes.len();
+        let close = move || (c('"'), count_min_max::<String, _, _>(n, n, c('#'))).map(|_| "\"");
+        many(not_followed_by(attempt(close())).with(any()))
+            .skip(close())
+            .map(Str)
+    })
+}
+
+fn character<I>() -> impl Parser<I, Output = Expr>
+where
+    I: Stream<Token = char>,
+    I::Error: ParseError<I::Token, I::Range, I::Position>,
+{
+    let name = many(satisfy(|c: char| c.is_alphanumeric()));
+
+    (attempt(string("#\\")), any(), name).and_then(|(_, first, rest): (_, char, String)| {
+        if rest.is_empty() {
+            return Ok(Char(first));
+        }
+
+        let name = format!("{}{}", first, rest);
+        let hex = match first {
+            'x' => u32::from_str_radix(&rest, 16).ok(),
+            _ => None,
+        };
+        CHAR_NAMES
+            .iter()
+            .find(|(char_name, _)| *char_name == name)
+            .map(|(_, c)| *c)
+            .or_else(|| hex.and_then(char::from_u32))
+            .map(Char)
+            .ok_or_else(|| StreamErrorFor::<I>::message_static_message("invalid character literal"))
+    })
 }
 
 parser! {
-    #[inline(always)]
-    fn expr[I]()(I) -> Expr
+    fn block_comment[I]()(I) -> ()
     where [I: Stream<Token = char>]
     {
-        let empty_list = attempt((c('('), spaces(), c(')'), spaces())).map(|_| Nil);
-        let list = between(c('(').skip(spaces()), c(')'), many(expr()))
-            .skip(spaces())
-            .map(Expr::List);
-
-        let quote = (c('\''), expr()).map(|(_, e)| Quote(Box::new(e)));
-        let quasiquote = (c('`'), expr()).map(|(_, e)| QuasiQuote(Box::new(e)));
-        let unquote = (c(','), expr()).map(|(_, e)| Unquote(Box::new(e)));
-
-
-        choice((
-            boolean(),
-            float(),
-            int(),
-            nil(),
-            symbol(),
-            empty_list,
-            sstring(),
+        let body = skip_many(choice((
+            attempt(block_comment()),
+            attempt((c('|'), not_followed_by(c('#')))).map(|_| ()),
+            attempt((c('#'), not_followed_by(c('|')))).map(|_| ()),
+            skip_many1(none_of("|#".chars())),
+        )));
+
+        (attempt(string("#|")), body, string("|#")).map(|_| ())
+    }
+}
+
+parser! {
+    fn whitespace[I]()(I) -> ()
+    where [I: Stream<Token = char, Position = position::SourcePosition>]
+    {
+        let line_comment = (c(';'), skip_many(satisfy(|c| c != '\n'))).map(|_| ());
+        let datum_comment = (attempt(string("#_")), whitespace(), expr()).map(|_| ());
+
+        skip_many(choice((
+            skip_many1(space()),
+            line_comment,
+            block_comment(),
+            datum_comment,
+        )))
+    }
+}
+
+// Orders the entries by key like the map itself, so that the span tree's
+// children line up with the map's iteration order.
+fn map_literal(items: Vec<(Expr, SpanTree)>) -> Result<(Expr, Vec<SpanTree>), &'static str> {
+    if !items.len().is_multiple_of(2) {
+        return Err("map literal must have an even number of forms");
+    }
+
+    let mut entries = vec![];
+    let mut items = items.into_iter();
+    while let (Some((key, key_tree)), Some((value, value_tree))) = (items.next(), items.next()) {
+        let key = Key::new(key).map_err(|_| "invalid map key")?;
+        entries.push((key, value, key_tree, value_tree));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if entries.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Err("duplicate map key");
+    }
+
+    let mut children = vec![];
+    let map = entries
+        .into_iter()
+        .map(|(key, value, key_tree, value_tree)| {
+            children.extend([key_tree, value_tree]);
+            (key, value)
+        })
+        .collect();
+    Ok((Map(map), children))
+}
+
+fn leaf(expr: Expr) -> (Expr, Vec<SpanTree>) {
+    (expr, vec![])
+}
+
+fn prefixed(
+    wrap: fn(Box<Expr>) -> Expr,
+) -> impl Fn((char, (Expr, SpanTree))) -> (Expr, Vec<SpanTree>) {
+    move |(_, (expr, tree))| (wrap(Box::new(expr)), vec![tree])
+}
+
+parser! {
+    #[inline(always)]
+    fn expr[I]()(I) -> (Expr, SpanTree)
+    where [I: Stream<Token = char, Position = position::SourcePosition>]
+    {
+        let list = between(c('(').skip(whitespace()), c(')'), many(expr().skip(whitespace())))
+            .map(|items: Vec<(Expr, SpanTree)>| {
+                let (exprs, children): (Vec<_>, _) = items.into_iter().unzip();
+                if exprs.is_empty() {
+                    (Nil, children)
+                } else {
+                    (Expr::List(exprs), children)
+                }
+            });
+
+        let vector = between(c('[').skip(whitespace()), c(']'), many(expr().skip(whitespace())))
+            .map(|items: Vec<(Expr, SpanTree)>| {
+                let (exprs, children): (Vec<_>, _) = items.into_iter().unzip();
+                (Vector(exprs.into()), children)
+            });
+
+        let map = between(c('{').skip(whitespace()), c('}'), many(expr().skip(whitespace())))
+            .and_then(|items| map_literal(items).map_err(StreamErrorFor::<I>::message_static_message));
+
+        let quote = (c('\''), expr()).map(prefixed(Quote));
+        let quasiquote = (c('`'), expr()).map(prefixed(QuasiQuote));
+        let unquote = (c(','), optional(c('@')), expr()).map(|(_, splicing, (expr, tree))| {
+            let expr = Box::new(expr);
+            match splicing {
+                Some(_) => (UnquoteSplicing(expr), vec![tree]),
+                None => (Unquote(expr), vec![tree]),
+            }
+        });
+
+        let form = choice((
+            raw_string().map(leaf),
+            atom().map(leaf),
+            sstring().map(leaf),
+            byte_string().map(leaf),
+            character().map(leaf),
             list,
+            vector,
+            map,
             quote,
             quasiquote,
             unquote,
-        ))
+        ));
+
+        (combine::position(), form, combine::position()).map(|(start, (expr, children), end)| {
+            let span = Span::new(to_position(start), to_position(end));
+            (expr, SpanTree { span, children })
+        })
     }
 }
 
-pub fn parse(input: &str) -> Result<Expr, String> {
-    match (spaces(), optional(expr()).skip(eof())).easy_parse(input) {
-        Ok(((_, Some(e)), _)) => Ok(e),
+pub fn parse(input: &str) -> Result<Expr, SyntaxError> {
+    let mut parser = (whitespace(), optional(expr().skip(whitespace()))).skip(eof());
+    match parser.easy_parse(position::Stream::new(input)) {
+        Ok(((_, Some((e, _))), _)) => Ok(e),
         Ok(((_, None), _)) => Ok(Nil),
-        Err(err) => Err(format!("{}", err)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn parse_forms(input: &str) -> Result<Vec<Form>, SyntaxError> {
+    let form = expr()
+        .skip(whitespace())
+        .map(|(expr, spans)| Form { expr, spans });
+    let mut parser = (whitespace(), many(form)).skip(eof());
+    match parser.easy_parse(position::Stream::new(input)) {
+        Ok(((_, forms), _)) => Ok(forms),
+        Err(err) => Err(err.into()),
     }
 }
+
+pub fn parse_program(input: &str) -> Result<Vec<Expr>, SyntaxError> {
+    parse_forms(input).map(|forms| forms.into_iter().map(|form| form.expr).collect())
+}
@@ -0,0 +1,196 @@
+use std::{fmt, rc::Rc};
+
+use crate::expr::Expr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    pub fn point(position: Position) -> Self {
+        let end = Position {
+            column: position.column + 1,
+            ..position
+        };
+        Self::new(position, end)
+    }
+
+    // Renders `message` as `name:line:col: error: message` followed by the
+    // first line of the span with the spanned columns underlined.
+    pub fn render(&self, source: &str, name: &str, message: &str) -> String {
+        let mut out = format!("{}:{}: error: {}", name, self.start, message);
+
+        let Some(line) = source.lines().nth(self.start.line.saturating_sub(1)) else {
+            return out;
+        };
+
+        let line_number = self.start.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let start = self.start.column.saturating_sub(1);
+        let end = if self.end.line == self.start.line {
+            self.end.column.saturating_sub(1)
+        } else {
+            line.chars().count()
+        };
+
+        let indent: String = line
+            .chars()
+            .take(start)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(end.saturating_sub(start).max(1));
+
+        out.push_str(&format!(
+            "\n{} |\n{} | {}\n{} | {}{}",
+            gutter, line_number, line, gutter, indent, carets
+        ));
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanTree {
+    pub span: Span,
+    pub children: Vec<SpanTree>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Form {
+    pub expr: Expr,
+    pub spans: SpanTree,
+}
+
+impl Form {
+    pub fn span(&self) -> Span {
+        self.spans.span
+    }
+
+    // Follows child indices down from the top of the form, stopping at the
+    // deepest node that exists.
+    pub fn span_at(&self, path: &[usize]) -> Span {
+        let mut tree = &self.spans;
+        for &index in path {
+            match tree.children.get(index) {
+                Some(child) => tree = child,
+                None => break,
+            }
+        }
+        tree.span
+    }
+}
+
+// Children in the same order as their `SpanTree` children. Map children are
+// stored in key order, matching the order of `map`.
+fn children(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::List(items) => items.iter().collect(),
+        Expr::Vector(items) => items.iter().collect(),
+        Expr::Map(map) => map.iter().flat_map(|(k, v)| [k.expr(), v]).collect(),
+        Expr::Quote(inner)
+        | Expr::QuasiQuote(inner)
+        | Expr::Unquote(inner)
+        | Expr::UnquoteSplicing(inner) => vec![inner.as_ref()],
+        _ => vec![],
+    }
+}
+
+// The forms of a top-level form, a lambda or macro body, or a macro
+// expansion, together with their span trees when they were read from text.
+#[derive(Debug, Clone)]
+pub struct Source {
+    inner: Rc<SourceInner>,
+}
+
+#[derive(Debug)]
+struct SourceInner {
+    exprs: Vec<Expr>,
+    spans: Option<Vec<SpanTree>>,
+}
+
+impl Source {
+    pub fn new(exprs: Vec<Expr>) -> Self {
+        Self {
+            inner: Rc::new(SourceInner { exprs, spans: None }),
+        }
+    }
+
+    pub(crate) fn with_spans(exprs: Vec<Expr>, spans: Vec<SpanTree>) -> Self {
+        Self {
+            inner: Rc::new(SourceInner {
+                exprs,
+                spans: Some(spans),
+            }),
+        }
+    }
+
+    pub fn exprs(&self) -> &[Expr] {
+        &self.inner.exprs
+    }
+
+    pub fn has_spans(&self) -> bool {
+        self.inner.spans.is_some()
+    }
+
+    // Finds the span of the node stored at `target`. Nodes are matched by
+    // address, so identical subforms are told apart.
+    pub(crate) fn span_of(&self, target: *const Expr) -> Option<Span> {
+        self.find(|expr, tree| std::ptr::eq(expr, target).then_some(tree.span))
+    }
+
+    // Finds the span trees of `run`, a run of consecutive items of one of the
+    // lists in the source, such as the body of a lambda.
+    pub(crate) fn spans_of(&self, run: &[Expr]) -> Option<Vec<SpanTree>> {
+        let first = run.first()?;
+        self.find(|expr, tree| {
+            let Expr::List(items) = expr else {
+                return None;
+            };
+            let start = items.iter().position(|item| std::ptr::eq(item, first))?;
+            tree.children
+                .get(start..start + run.len())
+                .map(|trees| trees.to_vec())
+        })
+    }
+
+    fn find<T>(&self, mut visit: impl FnMut(&Expr, &SpanTree) -> Option<T>) -> Option<T> {
+        let spans = self.inner.spans.as_ref()?;
+        find(self.inner.exprs.iter(), spans, &mut visit)
+    }
+}
+
+impl PartialEq for Source {
+    fn eq(&self, other: &Self) -> bool {
+        self.exprs() == other.exprs()
+    }
+}
+
+fn find<'a, T>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    trees: &[SpanTree],
+    visit: &mut impl FnMut(&Expr, &SpanTree) -> Option<T>,
+) -> Option<T> {
+    exprs.into_iter().zip(trees).find_map(|(expr, tree)| {
+        visit(expr, tree).or_else(|| match expr {
+            Expr::List(items) => find(items, &tree.children, visit),
+            _ => find(children(expr), &tree.children, visit),
+        })
+    })
+}
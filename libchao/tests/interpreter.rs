@@ -0,0 +1,1137 @@
+extern crate libchao;
+
+use libchao::{Expr::*, Interpreter};
+
+macro_rules! assert_eval {
+    ($input:expr, $expected:expr) => {
+        assert_eq!(Interpreter::new().eval_str($input).ok(), Some($expected))
+    };
+}
+
+macro_rules! assert_eval_err {
+    ($input:expr) => {
+        assert!(Interpreter::new().eval_str($input).is_err())
+    };
+}
+
+#[test]
+fn evaluates_programs() {
+    assert_eval!("", Nil);
+    assert_eval!("(def a 1) (def b 2) (+ a b)", Int(3));
+    assert_eval!("(def square (x) (* x x))\n(square 4)", Int(16));
+    assert_eval_err!("(def a 1) (");
+}
+
+#[test]
+fn evaluates_with_host_definitions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.define("*args*", List(vec![Str("a".to_string())]));
+    assert_eq!(
+        interpreter.eval_str("(first *args*)").ok(),
+        Some(Str("a".to_string()))
+    );
+}
+
+#[test]
+fn reports_error_positions() {
+    let source = "(def f (x) (+ x 1))\n(f 1)\n(list 1\n  (+ 1 \"a\"))";
+    let err = Interpreter::new().eval_str(source).unwrap_err();
+    assert_eq!(
+        err.render(source, "test.chao"),
+        "test.chao:4:3: error: type error: + cannot be applied to 1 (int) and \"a\" (string)\n  |\n4 |   (+ 1 \"a\"))\n  |   ^^^^^^^^^"
+    );
+
+    let source = "(def f (x) (+ x \"a\"))\n(f 1)";
+    let err = Interpreter::new().eval_str(source).unwrap_err();
+    assert_eq!(
+        err.render(source, "test.chao"),
+        "test.chao:1:12: error: type error: + cannot be applied to 1 (int) and \"a\" (string)\n  |\n1 | (def f (x) (+ x \"a\"))\n  |            ^^^^^^^^^"
+    );
+
+    let source = "(try (do 1 (+ 1 \"a\")) (catch 'other e 1))";
+    let err = Interpreter::new().eval_str(source).unwrap_err();
+    assert_eq!(
+        err.render(source, "test.chao"),
+        "test.chao:1:12: error: type error: + cannot be applied to 1 (int) and \"a\" (string)\n  |\n1 | (try (do 1 (+ 1 \"a\")) (catch 'other e 1))\n  |            ^^^^^^^^^"
+    );
+}
+
+#[test]
+fn reports_error_positions_in_bodies() {
+    use libchao::Position;
+
+    let position = |source: &str| {
+        Interpreter::new()
+            .eval_str(source)
+            .unwrap_err()
+            .span()
+            .map(|span| span.start)
+    };
+
+    assert_eq!(
+        position("(def helper (x)\n  (+ x \"oops\"))\n(def main () (helper 1))\n(main)"),
+        Some(Position { line: 2, column: 3 })
+    );
+    assert_eq!(
+        position("(def f (xs)\n  (map (lambda (x)\n    (+ x zz)) xs))\n(f '(1 2))"),
+        Some(Position {
+            line: 3,
+            column: 10
+        })
+    );
+    assert_eq!(
+        position("(defmacro bad (e)\n  (+ e \"x\"))\n(bad 1)"),
+        Some(Position { line: 2, column: 3 })
+    );
+    assert_eq!(
+        position("(defmacro twice (e) `(do ,e ,e))\n(def g ()\n  (twice (car 5)))\n(g)"),
+        Some(Position { line: 3, column: 3 })
+    );
+}
+
+#[test]
+fn reports_positions_of_identical_subforms() {
+    use libchao::Position;
+
+    let position = |source: &str| {
+        Interpreter::new()
+            .eval_str(source)
+            .unwrap_err()
+            .span()
+            .map(|span| span.start)
+    };
+
+    assert_eq!(
+        position("(let ((a 1))\n (+ a 1)\n (let ((a \"s\"))\n (+ a 1)))"),
+        Some(Position { line: 4, column: 2 })
+    );
+    assert_eq!(
+        position("(def a 1)\n(do (+ a 1) (set a \"s\") (+ a 1))"),
+        Some(Position {
+            line: 2,
+            column: 25
+        })
+    );
+    assert_eq!(
+        position("(cond (false (+ 1 \"a\"))\n      (true (+ 1 \"a\")))"),
+        Some(Position {
+            line: 2,
+            column: 13
+        })
+    );
+    assert_eq!(
+        position("[(+ 1 2) {:a (+ 1 \"b\")}]"),
+        Some(Position {
+            line: 1,
+            column: 14
+        })
+    );
+}
+
+#[test]
+fn evaluates_tail_calls_in_constant_stack() {
+    assert_eval!(
+        "(def loop (n) (if (= n 0) 0 (loop (- n 1))))\n(loop 20000)",
+        Int(0)
+    );
+    assert_eval!(
+        "(def even (n) (if (= n 0) true (odd (- n 1))))
+         (def odd (n) (if (= n 0) false (even (- n 1))))
+         (even 20001)",
+        Bool(false)
+    );
+    assert_eval!("(def x 1) (def f (x) (if x x x)) (f 2) x", Int(1));
+}
+
+#[test]
+fn evaluates_tail_calls_in_constant_memory() {
+    assert_eval!(
+        "(def loop (n) (if (= n 0) 'done (loop (- n 1))))\n(loop 2000000)",
+        Symbol("done".to_string())
+    );
+}
+
+#[test]
+fn binds_rest_parameters() {
+    assert_eval!(
+        "(def my-list (&rest xs) xs) (my-list 1 2 3)",
+        List(vec![Int(1), Int(2), Int(3)])
+    );
+    assert_eval!("(def my-list (&rest xs) xs) (my-list)", Nil);
+    assert_eval!("((lambda (a &rest b) b) 1 2)", List(vec![Int(2)]));
+    assert_eval!(
+        "(defmacro first-form (x &rest ignored) x) (first-form 1 (/ 1 0) 2)",
+        Int(1)
+    );
+    assert_eval_err!("((lambda (a &rest b) b))");
+    assert_eval_err!("(lambda (a &rest) a)");
+    assert_eval_err!("(lambda (&rest a b) a)");
+}
+
+#[test]
+fn binds_optional_parameters() {
+    assert_eval!("(def f (a &optional b) b) (f 1)", Nil);
+    assert_eval!("(def f (a &optional (b 2)) (+ a b)) (f 1)", Int(3));
+    assert_eval!("(def f (a &optional (b 2)) (+ a b)) (f 1 5)", Int(6));
+    assert_eval!("(def f (a &optional (b (* a 10))) b) (f 4)", Int(40));
+    assert_eval_err!("(def f (a &optional b) b) (f 1 2 3)");
+}
+
+#[test]
+fn binds_keyword_parameters() {
+    assert_eval!(
+        "(def area (&key (w 1) (h 1)) (* w h)) (area 'h 3 'w 2)",
+        Int(6)
+    );
+    assert_eval!("(def area (&key (w 1) (h 1)) (* w h)) (area 'h 3)", Int(3));
+    assert_eval!(
+        "(def f (&rest all &key a) all) (f 'a 1)",
+        List(vec![Symbol("a".to_string()), Int(1)])
+    );
+    assert_eval_err!("(def area (&key (w 1)) w) (area 'd 3)");
+    assert_eval_err!("(def area (&key (w 1)) w) (area 'w)");
+    assert_eval_err!("(lambda (&key a &rest b) a)");
+}
+
+#[test]
+fn evaluates_let_forms() {
+    assert_eval!("(let ((a 1) (b 2)) (+ a b))", Int(3));
+    assert_eval!("(let () 1)", Int(1));
+    assert_eval!("(def a 1) (let ((a 2) (b a)) b)", Int(1));
+    assert_eval!("(def a 1) (let ((a 2)) a) a", Int(1));
+    assert_eval!("(let* ((a 2) (b (* a 3))) b)", Int(6));
+    assert_eval!(
+        "(letrec ((even (lambda (n) (if (= n 0) true (odd (- n 1)))))
+                  (odd (lambda (n) (if (= n 0) false (even (- n 1))))))
+           (even 10))",
+        Bool(true)
+    );
+    assert_eval_err!("(let ((1 2)) 1)");
+    assert_eval_err!("(let ((a)) a)");
+    assert_eval_err!("(let (a 1) a)");
+}
+
+#[test]
+fn evaluates_sequences() {
+    assert_eval!("(do)", Nil);
+    assert_eval!("(do 1 2 3)", Int(3));
+    assert_eval!("(begin (def a 1) (set a (+ a 1)) a)", Int(2));
+    assert_eval!(
+        "(def counter 0)
+         (def bump (n) (set counter (+ counter n)) counter)
+         (bump 2) (bump 3)",
+        Int(5)
+    );
+    assert_eval!("((lambda () 1 2))", Int(2));
+    assert_eval!("(let ((a 1)) (def b 2) (+ a b))", Int(3));
+    assert_eval!(
+        "(defmacro twice (form) (def ignored 0) `(do ,form ,form))
+         (def n 0) (twice (set n (+ n 1))) n",
+        Int(2)
+    );
+    assert_eval!(
+        "(def loop (n) 1 (do 2 (if (= n 0) 0 (loop (- n 1))))) (loop 20000)",
+        Int(0)
+    );
+    assert_eval_err!("(lambda (x))");
+}
+
+#[test]
+fn evaluates_and_or() {
+    assert_eval!("(and)", Bool(true));
+    assert_eval!("(and 1 2 3)", Int(3));
+    assert_eval!("(and 1 nil (/ 1 0))", Nil);
+    assert_eval!("(and 1 false 3)", Bool(false));
+    assert_eval!("(or)", Nil);
+    assert_eval!("(or nil false 3)", Int(3));
+    assert_eval!("(or 1 (/ 1 0))", Int(1));
+    assert_eval!("(or nil false)", Bool(false));
+}
+
+#[test]
+fn evaluates_cond_and_case() {
+    assert_eval!(
+        "(def sign (n) (cond ((< n 0) 'negative) ((= n 0) 'zero) (else 'positive)))
+         (list (sign (- 1)) (sign 0) (sign 5))",
+        List(vec![
+            Symbol("negative".to_string()),
+            Symbol("zero".to_string()),
+            Symbol("positive".to_string()),
+        ])
+    );
+    assert_eval!("(cond (false 1))", Nil);
+    assert_eval!("(cond (nil 1) (42))", Int(42));
+    assert_eval!("(cond (true (def a 1) (+ a 1)))", Int(2));
+    assert_eval!(
+        "(def kind (x) (case x ((1 2 3) 'small) (10 'ten) (else 'other)))
+         (list (kind 2) (kind 10) (kind 11))",
+        List(vec![
+            Symbol("small".to_string()),
+            Symbol("ten".to_string()),
+            Symbol("other".to_string()),
+        ])
+    );
+    assert_eval!("(case 'b ((a) 1) ((b) 2))", Int(2));
+    assert_eval!("(case 5 ((1) 1))", Nil);
+    assert_eval_err!("(cond 1)");
+}
+
+#[test]
+fn evaluates_when_and_unless() {
+    assert_eval!("(when true 1 2)", Int(2));
+    assert_eval!("(when false (/ 1 0))", Nil);
+    assert_eval!("(unless false 1 2)", Int(2));
+    assert_eval!("(unless 0 (/ 1 0))", Nil);
+}
+
+#[test]
+fn raises_and_catches_errors() {
+    assert_eval!(
+        "(try (error \"boom\" 42) (catch e (list (error-kind e) (error-message e) (error-payload e))))",
+        List(vec![
+            Symbol("error".to_string()),
+            Str("boom".to_string()),
+            Int(42)
+        ])
+    );
+    assert_eval!(
+        "(try (error 'not-found \"missing\") (catch 'type-error e 1) (catch 'not-found e 2))",
+        Int(2)
+    );
+    assert_eval!(
+        "(try (+ 1 \"a\") (catch 'type-error e (error-payload e)))",
+        List(vec![Int(1), Str("a".to_string())])
+    );
+    assert_eval!("(try 1 2 (catch e 3))", Int(2));
+    assert_eval!(
+        "(try (try (error \"inner\") (catch 'type-error e 1)) (catch e (error-message e)))",
+        Str("inner".to_string())
+    );
+    assert_eval!(
+        "(try (try (error \"inner\") (catch e (error e))) (catch e (error-message e)))",
+        Str("inner".to_string())
+    );
+    assert_eval!("(error? (try (error \"x\") (catch e e)))", Bool(true));
+    assert_eval_err!("(try (error \"boom\") (catch 'type-error e 1))");
+    assert_eval_err!("(error 42)");
+}
+
+#[test]
+fn runs_finally_clauses() {
+    assert_eval!("(def log 0) (try 1 (finally (set log 1))) log", Int(1));
+    assert_eval!(
+        "(def log 0) (try (try (error \"x\") (finally (set log 1))) (catch e log))",
+        Int(1)
+    );
+    assert_eval!(
+        "(def log 0) (try (error \"x\") (catch e (set log 1)) (finally (set log (+ log 1))))",
+        Int(1)
+    );
+    assert_eval!(
+        "(def log 0) (try (error \"x\") (catch e (set log 1)) (finally (set log (+ log 1)))) log",
+        Int(2)
+    );
+}
+
+#[test]
+fn reports_offending_values() {
+    let err = Interpreter::new().eval_str("(* 2 'x)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type error: * cannot be applied to 2 (int) and x (symbol)"
+    );
+    assert_eq!(err.kind_name(), "type-error");
+
+    let err = Interpreter::new().eval_str("(+ 1 2 \"a\")").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type error: + cannot be applied to 1 (int) and 2 (int) and \"a\" (string)"
+    );
+
+    let err = Interpreter::new().eval_str("(car 5)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type error: car cannot be applied to 5 (int)"
+    );
+
+    let err = Interpreter::new().eval_str("(cdr 5)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "type error: cdr cannot be applied to 5 (int)"
+    );
+    assert_eval!(
+        "(try (- \"a\") (catch 'type-error e (error-payload e)))",
+        List(vec![Str("a".to_string())])
+    );
+}
+
+#[test]
+fn reports_unbound_variables() {
+    let err = Interpreter::new()
+        .eval_str("(def square (x) (* x x)) (squre 3)")
+        .unwrap_err();
+    assert_eq!(err.kind_name(), "unbound-variable");
+    assert_eq!(
+        err.to_string(),
+        "unbound variable squre, did you mean square?"
+    );
+
+    let err = Interpreter::new().eval_str("xyzzy").unwrap_err();
+    assert_eq!(err.to_string(), "unbound variable xyzzy");
+
+    let err = Interpreter::new().eval_str("(+ x 1)").unwrap_err();
+    assert_eq!(err.to_string(), "unbound variable x");
+
+    let err = Interpreter::new().eval_str("(mp + '(1))").unwrap_err();
+    assert_eq!(err.to_string(), "unbound variable mp, did you mean map?");
+
+    let err = Interpreter::new()
+        .eval_str("(let ((count 1)) (set cont 2))")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unbound variable cont, did you mean count?"
+    );
+
+    assert_eval!(
+        "(try undefined (catch 'unbound-variable e (error-payload e)))",
+        Symbol("undefined".to_string())
+    );
+}
+
+#[test]
+fn checks_bound_symbols() {
+    assert_eval!("(bound? 'nope)", Bool(false));
+    assert_eval!("(def yes 1) (bound? 'yes)", Bool(true));
+    assert_eval!("(bound? '+)", Bool(true));
+    assert_eval!("(let ((local 1)) (bound? 'local))", Bool(true));
+    assert_eval_err!("(bound? 1)");
+}
+
+#[test]
+fn splices_unquoted_lists() {
+    assert_eval!(
+        "(def xs (list 2 3)) `(1 ,@xs 4)",
+        List(vec![Int(1), Int(2), Int(3), Int(4)])
+    );
+    assert_eval!("`(,@nil)", Nil);
+    assert_eval!("`(1 ,@'())", List(vec![Int(1)]));
+    assert_eval!(
+        "(defmacro my-when (test &rest body) `(if ,test (do ,@body) nil))
+         (my-when true 1 2 3)",
+        Int(3)
+    );
+    assert_eval!(
+        "(def xs (list 1 2)) ``(a ,@xs)",
+        QuasiQuote(Box::new(List(vec![
+            Symbol("a".to_string()),
+            UnquoteSplicing(Box::new(Symbol("xs".to_string()))),
+        ])))
+    );
+    assert_eval!(
+        "(def xs (list 1 2)) ``(a ,(b ,@xs))",
+        QuasiQuote(Box::new(List(vec![
+            Symbol("a".to_string()),
+            Unquote(Box::new(List(vec![
+                Symbol("b".to_string()),
+                Int(1),
+                Int(2)
+            ]))),
+        ])))
+    );
+    assert_eval!(
+        "(def x 1) `(a ',x)",
+        List(vec![Symbol("a".to_string()), Quote(Box::new(Int(1)))])
+    );
+    assert_eval_err!("(def x 1) `,@x");
+    assert_eval_err!("(def x 1) `(,@x)");
+    assert_eval_err!(",@x");
+}
+
+#[test]
+fn expands_macros() {
+    let defs = "(defmacro my-unless (test &rest body) `(my-if ,test nil (do ,@body)))
+                (defmacro my-if (test then else) `(cond (,test ,then) (else ,else)))";
+
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(defs).unwrap();
+
+    let form = libchao::parse("(my-unless x (f (my-if a b c)))").unwrap();
+    assert_eq!(
+        format!("{:?}", interpreter.macroexpand_1(&form).unwrap().unwrap()),
+        "(my-if x nil (do (f (my-if a b c))))"
+    );
+    assert_eq!(
+        format!("{:?}", interpreter.macroexpand(&form).unwrap()),
+        "(cond (x nil) (else (do (f (my-if a b c)))))"
+    );
+    assert_eq!(
+        format!("{:?}", interpreter.expand_all(&form).unwrap()),
+        "(cond (x nil) (else (do (f (cond (a b) (else c))))))"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            interpreter
+                .expand_all(&libchao::parse("(list '(my-if a b c))").unwrap())
+                .unwrap()
+        ),
+        "(list '(my-if a b c))"
+    );
+    assert_eq!(
+        interpreter
+            .macroexpand_1(&libchao::parse("(f x)").unwrap())
+            .unwrap(),
+        None
+    );
+
+    let mut interpreter = Interpreter::new();
+    assert_eq!(
+        format!(
+            "{:?}",
+            interpreter
+                .eval_str(&format!("{} (macroexpand-1 '(my-unless x y))", defs))
+                .unwrap()
+        ),
+        "(my-if x nil (do y))"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            interpreter
+                .eval_str("(macroexpand '(my-unless x y))")
+                .unwrap()
+        ),
+        "(cond (x nil) (else (do y)))"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            interpreter
+                .eval_str("(macroexpand-all '(do (my-if a b c)))")
+                .unwrap()
+        ),
+        "(do (cond (a b) (else c)))"
+    );
+    assert_eval!(
+        "(macroexpand-1 '(+ 1 2))",
+        List(vec![Symbol("+".to_string()), Int(1), Int(2)])
+    );
+}
+
+#[test]
+fn expands_only_expression_positions() {
+    let defs = "(defmacro unless2 (c x) `(if ,c nil ,x))
+                (defmacro m (x) `(+ ,x 1))";
+    let expand = |form: &str| {
+        let mut interpreter = Interpreter::new();
+        interpreter.eval_str(defs).unwrap();
+        let form = libchao::parse(form).unwrap();
+        format!("{:?}", interpreter.expand_all(&form).unwrap())
+    };
+
+    assert_eq!(
+        expand("(def f (unless2 b) (+ unless2 b))"),
+        "(def f (unless2 b) (+ unless2 b))"
+    );
+    assert_eq!(expand("(def x (m 1))"), "(def x (+ 1 1))");
+    assert_eq!(expand("(lambda (m) (m 1))"), "(lambda (m) (+ 1 1))");
+    assert_eq!(expand("(defmacro n (m) (m 1))"), "(defmacro n (m) (+ 1 1))");
+    assert_eq!(expand("(let ((m 1)) m)"), "(let ((m 1)) m)");
+    assert_eq!(
+        expand("(let* ((a (m 1)) (m 2)) (m a))"),
+        "(let* ((a (+ 1 1)) (m 2)) (+ a 1))"
+    );
+    assert_eq!(
+        expand("(case k ((m) (m 1)) (m 2))"),
+        "(case k ((m) (+ 1 1)) (m 2))"
+    );
+    assert_eq!(expand("(cond (m (m 1)))"), "(cond (m (+ 1 1)))");
+    assert_eq!(
+        expand("(try (m 1) (catch 'm m (m 2)) (catch m m) (finally (m 3)))"),
+        "(try (+ 1 1) (catch 'm m (+ 2 1)) (catch m m) (finally (+ 3 1)))"
+    );
+
+    let mut interpreter = Interpreter::new();
+    interpreter.eval_str(defs).unwrap();
+    let form = libchao::parse("(let ((m 1)) (m m))").unwrap();
+    let expanded = interpreter.expand_all(&form).unwrap();
+    assert_eq!(interpreter.eval(&expanded), Ok(Int(2)));
+}
+
+#[test]
+fn generates_unique_symbols() {
+    assert_eval!("(= (gensym) (gensym))", Bool(false));
+    assert_eval!("(gensym 'tmp)", Symbol("tmp#1".to_string()));
+    assert_eval!("(symbol->string (gensym))", Str("G#1".to_string()));
+    assert_eval_err!("(intern \"G#1\")");
+    assert_eval_err!("(intern \"tmp#\")");
+    assert_eval_err!("G#1");
+    assert_eval_err!("(gensym 1)");
+}
+
+#[test]
+fn renames_auto_gensyms_in_templates() {
+    let form = Interpreter::new()
+        .eval_str("`(let ((tmp# 1)) (+ tmp# x#))")
+        .unwrap();
+    assert_eq!(format!("{:?}", form), "(let ((tmp#1 1)) (+ tmp#1 x#2))");
+    assert_eval!("(= `tmp# `tmp#)", Bool(false));
+    assert_eval!(
+        "(defmacro swap! (a b) `(let ((tmp# ,a)) (set ,a ,b) (set ,b tmp#)))
+         (def tmp 1) (def other 2)
+         (swap! tmp other)
+         (list tmp other)",
+        List(vec![Int(2), Int(1)])
+    );
+}
+
+#[test]
+fn builds_and_takes_apart_lists() {
+    assert_eval!("(list)", Nil);
+    assert_eval!("(cons 1 '(2 3))", List(vec![Int(1), Int(2), Int(3)]));
+    assert_eval!("(cons 1 nil)", List(vec![Int(1)]));
+    assert_eval!("(cons 1 ())", List(vec![Int(1)]));
+    assert_eval!("(car '(1 2))", Int(1));
+    assert_eval!("(first nil)", Nil);
+    assert_eval!("(cdr '(1 2))", List(vec![Int(2)]));
+    assert_eval!("(rest '(1))", Nil);
+    assert_eval!("(rest nil)", Nil);
+    assert_eval!("(nth '(1 2 3) 2)", Int(3));
+    assert_eval!("(nth '(1 2 3) 3)", Nil);
+    assert_eval!("(length '(1 2 3))", Int(3));
+    assert_eval!("(length nil)", Int(0));
+    assert_eval!(
+        "(append '(1) nil '(2 3) (list))",
+        List(vec![Int(1), Int(2), Int(3)])
+    );
+    assert_eval!("(append)", Nil);
+    assert_eval!("(reverse '(1 2 3))", List(vec![Int(3), Int(2), Int(1)]));
+    assert_eval!("(reverse nil)", Nil);
+    assert_eval_err!("(cons 1 2)");
+    assert_eval_err!("(car 1)");
+    assert_eval_err!("(nth '(1) 'a)");
+}
+
+#[test]
+fn checks_for_empty_lists() {
+    assert_eval!("(null? nil)", Bool(true));
+    assert_eval!("(null? ())", Bool(true));
+    assert_eval!("(null? (cdr '(1)))", Bool(true));
+    assert_eval!("(null? 0)", Bool(false));
+    assert_eval!("(empty? (list))", Bool(true));
+    assert_eval!("(empty? '(1))", Bool(false));
+    assert_eval_err!("(empty? 0)");
+    assert_eval!(
+        "(def sum (xs) (if (null? xs) 0 (+ (car xs) (sum (cdr xs))))) (sum '(1 2 3))",
+        Int(6)
+    );
+}
+
+#[test]
+fn applies_callables_from_rust() {
+    let mut interpreter = Interpreter::new();
+    let square = interpreter.eval_str("(lambda (x) (* x x))").unwrap();
+    let plus = interpreter.eval_str("+").unwrap();
+
+    let (Callable(square), Callable(plus)) = (square, plus) else {
+        panic!("expected callables");
+    };
+    assert_eq!(interpreter.apply(&square, vec![Int(7)]).ok(), Some(Int(49)));
+    assert_eq!(
+        interpreter.apply(&plus, vec![Int(1), Int(2)]).ok(),
+        Some(Int(3))
+    );
+    assert!(interpreter.apply(&square, vec![]).is_err());
+}
+
+#[test]
+fn applies_higher_order_functions() {
+    assert_eval!("(apply + '(1 2 3))", Int(6));
+    assert_eval!("(apply + 1 2 '(3))", Int(6));
+    assert_eval!(
+        "(map (lambda (x) (* x x)) '(1 2 3))",
+        List(vec![Int(1), Int(4), Int(9)])
+    );
+    assert_eval!("(map + '(1 2 3) '(10 20))", List(vec![Int(11), Int(22)]));
+    assert_eval!("(map + nil)", Nil);
+    assert_eval!(
+        "(filter (lambda (x) (> x 1)) '(1 2 3))",
+        List(vec![Int(2), Int(3)])
+    );
+    assert_eval!("(reduce + 0 '(1 2 3))", Int(6));
+    assert_eval!("(reduce + '(1 2 3))", Int(6));
+    assert_eval!("(reduce + nil)", Nil);
+    assert_eval!(
+        "(reduce (lambda (acc x) (cons x acc)) nil '(1 2 3))",
+        List(vec![Int(3), Int(2), Int(1)])
+    );
+    assert_eval!(
+        "(def total 0) (for-each (lambda (x) (set total (+ total x))) '(1 2 3)) total",
+        Int(6)
+    );
+    assert_eval!("(any? (lambda (x) (> x 2)) '(1 2 3))", Bool(true));
+    assert_eval!("(any? (lambda (x) (> x 3)) '(1 2 3))", Bool(false));
+    assert_eval!("(every? (lambda (x) (> x 0)) '(1 2 3))", Bool(true));
+    assert_eval!("(every? (lambda (x) (> x 1)) nil)", Bool(true));
+    assert_eval_err!("(map 1 '(1 2))");
+    assert_eval_err!("(map if '(1 2))");
+}
+
+#[test]
+fn sorts_lists() {
+    assert_eval!("(sort '(3 1 2))", List(vec![Int(1), Int(2), Int(3)]));
+    assert_eval!("(sort > '(3 1 2))", List(vec![Int(3), Int(2), Int(1)]));
+    assert_eval!("(sort nil)", Nil);
+    assert_eval!(
+        "(sort-by (lambda (x) (car (cdr x))) '((a 2) (b 1) (c 2)))",
+        List(vec![
+            List(vec![Symbol("b".to_string()), Int(1)]),
+            List(vec![Symbol("a".to_string()), Int(2)]),
+            List(vec![Symbol("c".to_string()), Int(2)]),
+        ])
+    );
+    assert_eval!(
+        "(sort (lambda (a b) true) '(1 2 3 4 5))",
+        List(vec![Int(5), Int(4), Int(3), Int(2), Int(1)])
+    );
+    assert_eval_err!("(sort '(1 \"a\"))");
+}
+
+#[test]
+fn manipulates_strings() {
+    assert_eval!(
+        r#"(str "a" 1 'b nil '(1 "c"))"#,
+        Str(r#"a1bnil(1 "c")"#.to_string())
+    );
+    assert_eval!(r#"(string-length "héllo")"#, Int(5));
+    assert_eval!(r#"(substring "héllo" 1 3)"#, Str("él".to_string()));
+    assert_eval!(r#"(substring "hello" 2)"#, Str("llo".to_string()));
+    assert_eval!(r#"(char-at "héllo" 1)"#, Char('é'));
+    assert_eval!(
+        r#"(split "a,b,c" ",")"#,
+        List(vec![
+            Str("a".to_string()),
+            Str("b".to_string()),
+            Str("c".to_string())
+        ])
+    );
+    assert_eval!(r#"(join '("a" "b" 1) ", ")"#, Str("a, b, 1".to_string()));
+    assert_eval!(r#"(trim "  hi  ")"#, Str("hi".to_string()));
+    assert_eval!(r#"(upcase "hi")"#, Str("HI".to_string()));
+    assert_eval!(r#"(contains? "hello" "ell")"#, Bool(true));
+    assert_eval!(r#"(starts-with? "hello" "lo")"#, Bool(false));
+    assert_eval!(r#"(replace "a-b-c" "-" "+")"#, Str("a+b+c".to_string()));
+    assert_eval_err!(r#"(substring "hello" 2 9)"#);
+    assert_eval_err!(r#"(char-at "hello" 5)"#);
+}
+
+#[test]
+fn converts_and_formats_strings() {
+    assert_eval!(r#"(string->number "42")"#, Int(42));
+    assert_eval!(r#"(string->number "1.5")"#, Float(1.5));
+    assert_eval!(r#"(string->number "abc")"#, Nil);
+    assert_eval!("(number->string 42)", Str("42".to_string()));
+    assert_eval!("(number->string 255 16)", Str("ff".to_string()));
+    assert_eval!(
+        r#"(format "~a + {} = ~s" 1 2 "three")"#,
+        Str(r#"1 + 2 = "three""#.to_string())
+    );
+    assert_eval!(r#"(format "~~{{}}")"#, Str("~{}".to_string()));
+    assert_eval_err!(r#"(format "~a {}" 1)"#);
+    assert_eval_err!(r#"(format "~a" 1 2)"#);
+}
+
+#[test]
+fn inspects_types() {
+    assert_eval!("(int? 1)", Bool(true));
+    assert_eval!("(int? 1.0)", Bool(false));
+    assert_eval!("(float? 1.0)", Bool(true));
+    assert_eval!("(number? 1.0)", Bool(true));
+    assert_eval!(r#"(string? "a")"#, Bool(true));
+    assert_eval!("(symbol? 'a)", Bool(true));
+    assert_eval!("(list? '(1))", Bool(true));
+    assert_eval!("(list? nil)", Bool(true));
+    assert_eval!("(nil? nil)", Bool(true));
+    assert_eval!("(nil? false)", Bool(false));
+    assert_eval!("(fn? car)", Bool(true));
+    assert_eval!("(fn? (lambda (x) x))", Bool(true));
+    assert_eval!(
+        "(defmacro m (x) x) (list (fn? m) (macro? m))",
+        List(vec![Bool(false), Bool(true)])
+    );
+    assert_eval!("(type-of 1.5)", Symbol("float".to_string()));
+    assert_eval!("(type-of '(1))", Symbol("list".to_string()));
+    assert_eval!("(type-of type-of)", Symbol("fn".to_string()));
+}
+
+#[test]
+fn converts_types() {
+    assert_eval!("(symbol->string 'abc)", Str("abc".to_string()));
+    assert_eval!(r#"(symbol->string (intern "abc"))"#, Str("abc".to_string()));
+    assert_eval!("(int 2.9)", Int(2));
+    assert_eval!("(int 0.0)", Int(0));
+    assert_eval!(r#"(int " 42 ")"#, Int(42));
+    assert_eval!("(float 2)", Float(2.0));
+    assert_eval!(r#"(float "2.5")"#, Float(2.5));
+    assert_eval!("(boolean nil)", Bool(false));
+    assert_eval!("(boolean 0)", Bool(true));
+    assert_eval_err!(r#"(int "abc")"#);
+    assert_eval_err!("(symbol->string 1)");
+}
+
+#[test]
+fn promotes_integers_on_overflow() {
+    assert_eval!(
+        "(number->string (* 9999999999 9999999999))",
+        Str("99999999980000000001".to_string())
+    );
+    assert_eval!(
+        "(number->string (- (- 0 9223372036854775807) 2))",
+        Str("-9223372036854775809".to_string())
+    );
+    assert_eval!("(- (+ 9223372036854775807 1) 1)", Int(i64::MAX));
+    assert_eval!("(- 9223372036854775808 1)", Int(i64::MAX));
+    assert_eval!("(int? (* 9999999999 9999999999))", Bool(true));
+    assert_eval!("(> (* 9999999999 9999999999) 1.0)", Bool(true));
+}
+
+#[test]
+fn divides_exactly() {
+    assert_eval!("(/ 6 3)", Int(2));
+    assert_eval!("(number->string (/ 1 3))", Str("1/3".to_string()));
+    assert_eval!("(+ (/ 1 3) (/ 2 3))", Int(1));
+    assert_eval!("(* (/ 1 2) 4)", Int(2));
+    assert_eval!("(ratio? (/ 1 2))", Bool(true));
+    assert_eval!("(type-of (/ 1 2))", Symbol("ratio".to_string()));
+    assert_eval!("(< (/ 1 3) (/ 1 2))", Bool(true));
+    assert_eval!("(/ 1 2.0)", Float(0.5));
+    assert_eval!("(+ (/ 1 2) 0.25)", Float(0.75));
+    assert_eval!("(/ 1.0 0)", Float(f64::INFINITY));
+    assert_eval!(
+        "(error-kind (try (/ 1 0) (catch e e)))",
+        Symbol("division-by-zero".to_string())
+    );
+    assert_eval_err!("(/ (/ 1 2) 0)");
+    assert_eval!("(+ 1/3 2/3)", Int(1));
+    assert_eval!("(= (/ 1 3) 1/3)", Bool(true));
+}
+
+#[test]
+fn converts_exactness() {
+    assert_eval!("(exact->inexact (/ 1 4))", Float(0.25));
+    assert_eval!("(exact->inexact 3)", Float(3.0));
+    assert_eval!("(= (inexact->exact 0.5) (/ 1 2))", Bool(true));
+    assert_eval!("(inexact->exact 2.0)", Int(2));
+    assert_eval!(
+        "(list (exact? (/ 1 2)) (inexact? 0.5))",
+        List(vec![Bool(true), Bool(true)])
+    );
+    assert_eval!("(int (/ 7 2))", Int(3));
+    assert_eval!("(float (/ 1 8))", Float(0.125));
+}
+
+#[test]
+fn compares_in_chains() {
+    assert_eval!("(< 1 2 3)", Bool(true));
+    assert_eval!("(< 1 3 2)", Bool(false));
+    assert_eval!("(> 3 2 1)", Bool(true));
+    assert_eval!("(<= 1 1 2)", Bool(true));
+    assert_eval!("(>= 2 2 3)", Bool(false));
+    assert_eval!("(< 1 1.5 (/ 7 4))", Bool(true));
+    assert_eval!("(!= 1 2)", Bool(true));
+    assert_eval!("(!= 'a 'a)", Bool(false));
+    assert_eval!("(= 1 1.0)", Bool(true));
+    assert_eval!("(= 1/2 0.5 (/ 2 4))", Bool(true));
+    assert_eval!("(= 9223372036854775808 9223372036854775808.0)", Bool(true));
+    assert_eval!("(!= 1 1.0)", Bool(false));
+    assert_eval!("(= 1 \"1\")", Bool(false));
+    assert_eval!("(= '(1) '(1.0))", Bool(false));
+    assert_eval_err!("(< 1 \"a\")");
+}
+
+#[test]
+fn computes_remainders() {
+    assert_eval!("(rem 7 3)", Int(1));
+    assert_eval!("(rem (- 0 7) 3)", Int(-1));
+    assert_eval!("(mod (- 0 7) 3)", Int(2));
+    assert_eval!("(mod 7 (- 0 3))", Int(-2));
+    assert_eval!("(% 7 2)", Int(1));
+    assert_eval!("(mod 7.5 2)", Float(1.5));
+    assert_eval_err!("(mod 1 0)");
+}
+
+#[test]
+fn computes_math_functions() {
+    assert_eval!("(abs (- 0 3))", Int(3));
+    assert_eval!("(= (abs (/ (- 0 1) 2)) (/ 1 2))", Bool(true));
+    assert_eval!("(min 3 1 2)", Int(1));
+    assert_eval!("(max 1 2.5 2)", Float(2.5));
+    assert_eval!("(expt 2 10)", Int(1024));
+    assert_eval!("(= (expt 2 (- 0 2)) (/ 1 4))", Bool(true));
+    assert_eval!(
+        "(number->string (expt 2 100))",
+        Str("1267650600228229401496703205376".to_string())
+    );
+    assert_eval!("(expt 4 0.5)", Float(2.0));
+    assert_eval!("(sqrt 16)", Int(4));
+    assert_eval!("(sqrt 2.25)", Float(1.5));
+    assert_eval!("(exp 0)", Float(1.0));
+    assert_eval!("(log 8 2)", Float(3.0));
+    assert_eval!("(sin 0)", Float(0.0));
+    assert_eval!("(atan 0 1)", Float(0.0));
+    assert_eval_err!("(min 1 'a)");
+    assert_eval_err!("(expt 0 (- 0 1))");
+    assert_eval!("(expt 1 2147483647)", Int(1));
+    assert_eval!("(expt (- 0 1) 2147483647)", Int(-1));
+    assert_eval!("(> (expt 2 100000) (expt 2 99999))", Bool(true));
+    assert_eval!(
+        "(error-kind (try (expt 10 2147483647) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+    assert_eval!(
+        "(error-kind (try (expt 10 2147483648) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+    assert_eval!(
+        "(error-kind (try (expt 1 2147483648) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+    assert_eval!(
+        "(error-kind (try (expt (/ 1 3) (- 0 100000000)) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+}
+
+#[test]
+fn rounds_numbers() {
+    assert_eval!("(floor 2.5)", Float(2.0));
+    assert_eval!("(ceil (/ 5 2))", Int(3));
+    assert_eval!("(floor (/ (- 0 5) 2))", Int(-3));
+    assert_eval!("(round (/ 5 2))", Int(3));
+    assert_eval!("(truncate (/ (- 0 5) 2))", Int(-2));
+    assert_eval!("(round 7)", Int(7));
+}
+
+#[test]
+fn operates_on_bits() {
+    assert_eval!("(bit-and 12 10)", Int(8));
+    assert_eval!("(bit-or 12 10 1)", Int(15));
+    assert_eval!("(bit-xor 12 10)", Int(6));
+    assert_eval!("(& 12 10)", Int(8));
+    assert_eval!("(bit-not 0)", Int(-1));
+    assert_eval!("(bit-shift-left 1 4)", Int(16));
+    assert_eval!("(bit-shift-right 256 4)", Int(16));
+    assert_eval!("(bit-shift-right (- 0 1) 100)", Int(-1));
+    assert_eval!(
+        "(number->string (bit-shift-left 1 64))",
+        Str("18446744073709551616".to_string())
+    );
+    assert_eval_err!("(bit-and 1 1.0)");
+    assert_eval!(
+        "(error-kind (try (bit-shift-left 1 100000000000000) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+}
+
+#[test]
+fn evaluates_vectors_and_maps() {
+    assert_eval!("[1 (+ 1 1)]", Vector(vec![Int(1), Int(2)].into()));
+    assert_eval!("(get {'a (+ 1 1)} 'a)", Int(2));
+    assert_eval!("(get {\"k\" 1} \"x\" 'none)", Symbol("none".to_string()));
+    assert_eval!("(get [1 2 3] 1)", Int(2));
+    assert_eval!("(get [1 2 3] 3)", Nil);
+    assert_eval!("(get nil 'a)", Nil);
+    assert_eval!("(= {1 'a 2 'b} {2 'b 1 'a})", Bool(true));
+    assert_eval!("(= [1 2] '(1 2))", Bool(false));
+    assert_eval!(
+        "(let ((x 1)) `[,x ,@(list 2 3)])",
+        Vector(vec![Int(1), Int(2), Int(3)].into())
+    );
+    assert_eval!("(get `{a ,(+ 1 2)} 'a)", Int(3));
+    assert_eval!("(type-of {})", Symbol("map".to_string()));
+    assert_eval!("(length [1 2 3])", Int(3));
+    assert_eval!(
+        "(vector->list (list->vector '(1 2)))",
+        List(vec![Int(1), Int(2)])
+    );
+    assert_eval_err!("{car 1}");
+}
+
+#[test]
+fn treats_vectors_as_sequences() {
+    assert_eval!("(first [1 2])", Int(1));
+    assert_eval!("(rest [1 2 3])", List(vec![Int(2), Int(3)]));
+    assert_eval!("(rest [1])", Nil);
+    assert_eval!("(nth [1 2 3] 2)", Int(3));
+    assert_eval!("(empty? [])", Bool(true));
+    assert_eval!(
+        "(filter (lambda (x) (> x 1)) [1 2 3])",
+        List(vec![Int(2), Int(3)])
+    );
+    assert_eval!("(map + [1 2] '(10 20))", List(vec![Int(11), Int(22)]));
+    assert_eval!("(reduce + [1 2 3])", Int(6));
+    assert_eval!("(reduce + 10 [])", Int(10));
+    assert_eval!("(sort [3 1 2])", List(vec![Int(1), Int(2), Int(3)]));
+    assert_eval!("(apply + [1 2])", Int(3));
+    assert_eval!("(cons 0 [1])", List(vec![Int(0), Int(1)]));
+}
+
+#[test]
+fn updates_maps_persistently() {
+    assert_eval!(
+        "(def m {'a 1}) (def n (assoc m 'b 2)) (list (keys m) (keys n))",
+        List(vec![
+            List(vec![Symbol("a".to_string())]),
+            List(vec![Symbol("a".to_string()), Symbol("b".to_string())]),
+        ])
+    );
+    assert_eval!("(vals (assoc nil 'a 1 'b 2))", List(vec![Int(1), Int(2)]));
+    assert_eval!(
+        "(keys (dissoc {'a 1 'b 2} 'a))",
+        List(vec![Symbol("b".to_string())])
+    );
+    assert_eval!("(keys {})", Nil);
+    assert_eval!("(contains? {'a nil} 'a)", Bool(true));
+    assert_eval!("(contains? [1 2] 2)", Bool(false));
+    assert_eval!("(contains? \"hello\" \"ell\")", Bool(true));
+    assert_eval!("(get (update {'n 1} 'n + 10) 'n)", Int(11));
+    assert_eval!(
+        "(get (update {} 'n (lambda (n) (if n (+ n 1) 1))) 'n)",
+        Int(1)
+    );
+    assert_eval!(
+        "(vals (merge {'a 1 'b 2} nil {'b 3}))",
+        List(vec![Int(1), Int(3)])
+    );
+    assert_eval!(
+        "(def v [1 2]) (list v (assoc v 0 'x) (assoc v 2 3))",
+        List(vec![
+            Vector(vec![Int(1), Int(2)].into()),
+            Vector(vec![Symbol("x".to_string()), Int(2)].into()),
+            Vector(vec![Int(1), Int(2), Int(3)].into()),
+        ])
+    );
+    assert_eval_err!("(assoc [1 2] 5 0)");
+    assert_eval_err!("(assoc {} 'a)");
+}
+
+#[test]
+fn evaluates_keywords() {
+    assert_eval!(":a", Keyword("a".to_string()));
+    assert_eval!("(= :a :a)", Bool(true));
+    assert_eval!("(= :a 'a)", Bool(false));
+    assert_eval!("(:b {:a 1 :b 2})", Int(2));
+    assert_eval!("(:c {:a 1} 'none)", Symbol("none".to_string()));
+    assert_eval!("(:a nil)", Nil);
+    assert_eval!("(get {:a 1} :a)", Int(1));
+    assert_eval!("(keyword? :a)", Bool(true));
+    assert_eval!("(keyword \"a\")", Keyword("a".to_string()));
+    assert_eval!("(keyword 'a)", Keyword("a".to_string()));
+    assert_eval!("(name :a)", Str("a".to_string()));
+    assert_eval!("(name 'a)", Str("a".to_string()));
+    assert_eval!("(type-of :a)", Symbol("keyword".to_string()));
+    assert_eval_err!("(:a 1)");
+    assert_eval_err!("(:a {} 1 2)");
+    assert_eval!("(map :a (list {:a 1} {:a 2}))", List(vec![Int(1), Int(2)]));
+    assert_eval!("(apply :a '({:a 1}))", Int(1));
+    assert_eval!("(apply :b {:a 1} '(none))", Symbol("none".to_string()));
+    assert_eval!("(length (filter :ok [{:ok true} {:ok false}]))", Int(1));
+    assert_eval!(
+        "(map :n (sort-by :n (list {:n 2} {:n 1})))",
+        List(vec![Int(1), Int(2)])
+    );
+    assert_eval!("(:m (update {:m {:a 1}} :m :a))", Int(1));
+    assert_eval_err!("(map :a (list 1))");
+}
+
+#[test]
+fn binds_keyword_arguments_by_keyword() {
+    assert_eval!(
+        "(def area (&key (w 1) (h 1)) (* w h)) (list (area :w 3 :h 4) (area 'w 5))",
+        List(vec![Int(12), Int(5)])
+    );
+    assert_eval!(
+        "(try (error :oops \"bad\") (catch :oops e (error-kind e)))",
+        Symbol("oops".to_string())
+    );
+}
+
+#[test]
+fn converts_characters() {
+    assert_eval!(r"#\a", Char('a'));
+    assert_eval!(r"(char->int #\A)", Int(65));
+    assert_eval!("(char->int (char-at \"😀\" 0))", Int(0x1F600));
+    assert_eval!("(int->char 233)", Char('é'));
+    assert_eval!(
+        r#"(string->list "añ😀")"#,
+        List(vec![Char('a'), Char('ñ'), Char('😀')])
+    );
+    assert_eval!(r#"(string->list "")"#, Nil);
+    assert_eval!(r"(list->string (list #\h #\i))", Str("hi".to_string()));
+    assert_eval!(r#"(str #\a "b" #\space)"#, Str("ab ".to_string()));
+    assert_eval!(r"(< #\a #\b)", Bool(true));
+    assert_eval!(r"(char? #\a)", Bool(true));
+    assert_eval!(r#"(char? "a")"#, Bool(false));
+    assert_eval!(r"(type-of #\a)", Symbol("char".to_string()));
+    assert_eval_err!("(int->char 55296)");
+    assert_eval_err!("(int->char -1)");
+    assert_eval_err!(r#"(list->string '(#\a "b"))"#);
+}
+
+#[test]
+fn manipulates_bytes() {
+    assert_eval!(r#"#b"\x01""#, Bytes(vec![1]));
+    assert_eval!("(bytes 1 2 255)", Bytes(vec![1, 2, 255]));
+    assert_eval!(r#"(get #b"abc" 1)"#, Int(98));
+    assert_eval!(r#"(get #b"abc" 3)"#, Nil);
+    assert_eval!(r#"(length #b"abc")"#, Int(3));
+    assert_eval!(r#"(subbytes #b"hello" 1 3)"#, Bytes(b"el".to_vec()));
+    assert_eval!(
+        r#"(bytes-append #b"ab" (bytes 0) #b"")"#,
+        Bytes(b"ab\0".to_vec())
+    );
+    assert_eval!(r#"(bytes->list #b"ab")"#, List(vec![Int(97), Int(98)]));
+    assert_eval!(r#"(string->bytes "é")"#, Bytes(vec![0xc3, 0xa9]));
+    assert_eval!(r#"(bytes->string #b"\xc3\xa9")"#, Str("é".to_string()));
+    assert_eval!(r#"(bytes? #b"")"#, Bool(true));
+    assert_eval!(r#"(= #b"a" (bytes 97))"#, Bool(true));
+    assert_eval_err!(r#"(bytes->string #b"\xff")"#);
+    assert_eval_err!(r#"(subbytes #b"abc" 2 4)"#);
+    assert_eval_err!("(bytes 256)");
+    assert_eval!(
+        r#"(try (bytes->string #b"\xff") (catch :invalid-encoding e (error-payload e)))"#,
+        Bytes(vec![0xff])
+    );
+}
+
+#[test]
+fn encodes_and_packs_bytes() {
+    assert_eval!(r#"(bytes->hex #b"\x00\xab")"#, Str("00ab".to_string()));
+    assert_eval!(r#"(hex->bytes "00AB")"#, Bytes(vec![0x00, 0xab]));
+    assert_eval!(r#"(bytes->base64 #b"hi!")"#, Str("aGkh".to_string()));
+    assert_eval!(r#"(base64->bytes "aGk=")"#, Bytes(b"hi".to_vec()));
+    assert_eval_err!(r#"(hex->bytes "abc")"#);
+    assert_eval_err!(r#"(base64->bytes "!!")"#);
+
+    assert_eval!("(int->bytes 258 2)", Bytes(vec![1, 2]));
+    assert_eval!("(int->bytes 258 4 :little)", Bytes(vec![2, 1, 0, 0]));
+    assert_eval!("(int->bytes -2 2)", Bytes(vec![0xff, 0xfe]));
+    assert_eval!("(int->bytes 255 1)", Bytes(vec![0xff]));
+    assert_eval_err!("(int->bytes 256 1)");
+    assert_eval_err!("(int->bytes -129 1)");
+    assert_eval_err!("(int->bytes 1 2 :middle)");
+    assert_eval!(
+        "(error-kind (try (int->bytes 1 100000000000000) (catch e e)))",
+        Symbol("limit-exceeded".to_string())
+    );
+
+    assert_eval!(r#"(bytes->int #b"\x01\x02")"#, Int(258));
+    assert_eval!(r#"(bytes->int #b"\x01\x02" :little)"#, Int(513));
+    assert_eval!(r#"(bytes->int #b"\xff\xfe" :big true)"#, Int(-2));
+    assert_eval!(r#"(bytes->int #b"")"#, Int(0));
+    assert_eval!(
+        "(= (bytes->int (int->bytes (expt 2 70) 9 :little) :little) (expt 2 70))",
+        Bool(true)
+    );
+}
@@ -36,6 +36,18 @@ fn parse_symbols() {
     assert_parse!("symbol", Symbol("symbol".to_string()));
     assert_parse_err!("sym bol");
     assert_parse!("+-/*%|&", Symbol("+-/*%|&".to_string()));
+    assert_parse!("macroexpand-1", Symbol("macroexpand-1".to_string()));
+    assert_parse!("tmp#", Symbol("tmp#".to_string()));
+    assert_parse_err!("tmp#x");
+    assert_parse!("nil?", Symbol("nil?".to_string()));
+    assert_parse!("trueish", Symbol("trueish".to_string()));
+    assert_parse!("list->vector", Symbol("list->vector".to_string()));
+    assert_parse!("set!", Symbol("set!".to_string()));
+    assert_parse!("vec2", Symbol("vec2".to_string()));
+    assert_parse!("-", Symbol("-".to_string()));
+    assert_parse!("-x", Symbol("-x".to_string()));
+    assert_parse!("_private", Symbol("_private".to_string()));
+    assert_parse!("a.b", Symbol("a.b".to_string()));
 }
 
 #[test]
@@ -45,6 +57,36 @@ fn parses_integers() {
     assert_parse_err!("0xz2a");
     assert_parse!("0b101010", Int(42));
     assert_parse_err!("0b2101010");
+    assert_parse!("-5", Int(-5));
+    assert_parse!("+3", Int(3));
+    assert_parse!("0o52", Int(42));
+    assert_parse!("-0x2a", Int(-42));
+    assert_parse!("1_000_000", Int(1_000_000));
+    assert_parse!("9223372036854775807", Int(i64::MAX));
+    assert_parse!("-9223372036854775808", Int(i64::MIN));
+    assert_parse!(
+        "9223372036854775808",
+        BigInt(num_bigint::BigInt::from(i64::MAX) + 1)
+    );
+    assert_parse!(
+        "-0x1_0000_0000_0000_0000",
+        BigInt(num_bigint::BigInt::from(-1) << 64)
+    );
+    assert_parse_err!("0x");
+    assert_parse_err!("12abc");
+    assert_parse_err!("0o8");
+}
+
+#[test]
+fn parses_ratios() {
+    let ratio = |n: i64, d: i64| Ratio(num_rational::BigRational::new(n.into(), d.into()));
+    assert_parse!("1/3", ratio(1, 3));
+    assert_parse!("-2/4", ratio(-1, 2));
+    assert_parse!("4/2", Int(2));
+    assert_parse_err!("1/0");
+    assert_parse_err!("1/-2");
+    assert_parse_err!("0x1/2");
+    assert_parse_err!("1.5/2");
 }
 
 #[test]
@@ -55,6 +97,13 @@ fn parses_floats() {
     assert_parse!("10.000000", Float(10.0));
     assert_parse!("10.0000001", Float(10.0000001));
     assert_parse_err!("1 .0");
+    assert_parse!("+3.2", Float(3.2));
+    assert_parse!("-0.5", Float(-0.5));
+    assert_parse!("1e10", Float(1e10));
+    assert_parse!("1.5E-3", Float(1.5e-3));
+    assert_parse!("1_000.5", Float(1000.5));
+    assert_parse_err!("1e");
+    assert_parse_err!("1.5.3");
 }
 
 #[test]
@@ -106,6 +155,15 @@ fn parses_quotes() {
     );
     assert_parse!("`(1)", QuasiQuote(Box::new(List(vec![Int(1)]))));
     assert_parse!(",(1)", Unquote(Box::new(List(vec![Int(1)]))));
+    assert_parse!(",@xs", UnquoteSplicing(Box::new(Symbol("xs".to_string()))));
+    assert_parse!(
+        "`(1 ,@xs)",
+        QuasiQuote(Box::new(List(vec![
+            Int(1),
+            UnquoteSplicing(Box::new(Symbol("xs".to_string())))
+        ])))
+    );
+    assert_parse_err!(",@");
 }
 
 #[test]
@@ -124,3 +182,158 @@ fn parses_strings() {
     assert_parse_err!(r#"\"""#);
     assert_parse_err!(r#""\foo""#);
 }
+
+#[test]
+fn parses_unicode_escapes_and_raw_strings() {
+    assert_parse!(r#""\x41\u{e9}\u{1F600}""#, Str("Aé😀".to_string()));
+    assert_parse!(r#""a\0b""#, Str("a\0b".to_string()));
+    assert_parse!("\"two\nlines\"", Str("two\nlines".to_string()));
+    assert_parse!(r#"r"C:\dir\n""#, Str(r"C:\dir\n".to_string()));
+    assert_parse!(r##"r#"say "hi""#"##, Str(r#"say "hi""#.to_string()));
+    assert_parse!("r", Symbol("r".to_string()));
+    assert_parse!("r#", Symbol("r#".to_string()));
+    assert_parse_err!(r#""\x4""#);
+    assert_parse_err!(r#""\u{110000}""#);
+    assert_parse_err!(r#""\u{D800}""#);
+    assert_parse_err!(r##"r#"open""##);
+}
+
+#[test]
+fn parses_characters() {
+    assert_parse!(r"#\a", Char('a'));
+    assert_parse!(r"#\é", Char('é'));
+    assert_parse!(r"#\(", Char('('));
+    assert_parse!(r"#\space", Char(' '));
+    assert_parse!(r"#\newline", Char('\n'));
+    assert_parse!(r"#\x41", Char('A'));
+    assert_parse!(r"#\x", Char('x'));
+    assert_parse!(r"(#\a #\b)", List(vec![Char('a'), Char('b')]));
+    assert_parse_err!(r"#\bogus");
+    assert_parse_err!(r"#\xD800");
+}
+
+#[test]
+fn parses_byte_strings() {
+    assert_parse!(r#"#b"""#, Bytes(vec![]));
+    assert_parse!(r#"#b"hi\n""#, Bytes(b"hi\n".to_vec()));
+    assert_parse!(r#"#b"\x00\xff\"""#, Bytes(vec![0x00, 0xff, b'"']));
+    assert_parse_err!(r#"#b"é""#);
+    assert_parse_err!(r#"#b"\u{e9}""#);
+    assert_parse_err!(r#"#b"open"#);
+}
+
+#[test]
+fn parses_programs() {
+    assert_eq!(libchao::parse_program(""), Ok(vec![]));
+    assert_eq!(libchao::parse_program(" \n "), Ok(vec![]));
+    assert_eq!(
+        libchao::parse_program("(def a 1) (def b 2)\n42"),
+        Ok(vec![
+            List(vec![
+                Symbol("def".to_string()),
+                Symbol("a".to_string()),
+                Int(1)
+            ]),
+            List(vec![
+                Symbol("def".to_string()),
+                Symbol("b".to_string()),
+                Int(2)
+            ]),
+            Int(42),
+        ])
+    );
+    assert!(libchao::parse_program("(def a 1) (def b").is_err());
+}
+
+#[test]
+fn parses_form_spans() {
+    use libchao::{Position, Span};
+
+    let span = |start: (usize, usize), end: (usize, usize)| {
+        Span::new(
+            Position {
+                line: start.0,
+                column: start.1,
+            },
+            Position {
+                line: end.0,
+                column: end.1,
+            },
+        )
+    };
+
+    let forms = libchao::parse_forms("(def a 1)\n  (foo\n  bar)").unwrap();
+    assert_eq!(forms.len(), 2);
+    assert_eq!(forms[0].span(), span((1, 1), (1, 10)));
+    assert_eq!(forms[1].span(), span((2, 3), (3, 7)));
+    assert_eq!(forms[1].span_at(&[1]), span((3, 3), (3, 6)));
+    assert_eq!(forms[1].span_at(&[1, 0]), span((3, 3), (3, 6)));
+
+    let err = libchao::parse_forms("(def a 1)\n(def b").unwrap_err();
+    assert_eq!(err.position, Position { line: 2, column: 7 });
+}
+
+#[test]
+fn parses_line_comments() {
+    assert_parse!("; nothing here", Nil);
+    assert_parse!("42 ; the answer", Int(42));
+    assert_parse!(
+        "(+ 1 ; one\n 2) ; done",
+        List(vec![Symbol("+".to_string()), Int(1), Int(2)])
+    );
+    assert_parse!(r#""; not a comment""#, Str("; not a comment".to_string()));
+}
+
+#[test]
+fn parses_block_comments() {
+    assert_parse!("#| comment |#", Nil);
+    assert_parse!("#| outer #| inner |# still outer |# 1", Int(1));
+    assert_parse!("(1 #| two\n |# 3)", List(vec![Int(1), Int(3)]));
+    assert_parse!("#|a|b#c|# 1", Int(1));
+    assert_parse_err!("#| unterminated");
+    assert_parse_err!("#| outer #| inner |# 1");
+}
+
+#[test]
+fn parses_datum_comments() {
+    assert_parse!("#_1 2", Int(2));
+    assert_parse!("#_ (a b c) 2", Int(2));
+    assert_parse!("(1 #_2 3)", List(vec![Int(1), Int(3)]));
+    assert_parse!("(#_1)", Nil);
+    assert_parse!("#_ #_ 1 2 3", Int(3));
+    assert_parse_err!("#_");
+}
+
+#[test]
+fn parses_vectors_and_maps() {
+    assert_parse!("[]", Vector(vec![].into()));
+    assert_parse!(
+        "[1 (a) [2]]",
+        Vector(
+            vec![
+                Int(1),
+                List(vec![Symbol("a".to_string())]),
+                Vector(vec![Int(2)].into())
+            ]
+            .into()
+        )
+    );
+    assert_parse!("{}", Map(Default::default()));
+    assert_eq!(
+        format!("{:?}", libchao::parse("{b 2 a [1]}").unwrap()),
+        "{a [1] b 2}"
+    );
+    assert_parse_err!("{a}");
+    assert_parse_err!("{a 1 a 2}");
+    assert_parse_err!("[1 2");
+}
+
+#[test]
+fn parses_keywords() {
+    assert_parse!(":name", Keyword("name".to_string()));
+    assert_parse!(":list->vector", Keyword("list->vector".to_string()));
+    assert_parse!(":1", Keyword("1".to_string()));
+    assert_parse_err!(":");
+    assert_parse_err!("::a");
+    assert_parse_err!(":a#");
+}